@@ -1,5 +1,5 @@
 use criterion::{criterion_group, criterion_main, Criterion};
-use norad::Font;
+use norad::{DataRequest, Font};
 use tempfile::tempdir;
 
 fn criterion_benchmark(c: &mut Criterion) {
@@ -7,12 +7,30 @@ fn criterion_benchmark(c: &mut Criterion) {
         b.iter(|| Font::load("testdata/Roboto-Regular.ufo").expect("font should load"));
     });
     let roboto_regular = Font::load("testdata/Roboto-Regular.ufo").unwrap();
+    c.bench_function("clone Roboto-Regular.ufo", |b| {
+        b.iter_with_large_drop(|| roboto_regular.clone());
+    });
     c.bench_function("write Roboto-Regular.ufo", |b| {
         b.iter_with_large_drop(|| {
             let write_dir = tempdir().unwrap();
             roboto_regular.save(write_dir.path()).expect("font should save");
         });
     });
+
+    // A font with a multi-megabyte feature file, to compare eager and lazy loading.
+    let mut big_features = Font::load("testdata/MutatorSansLightWide.ufo").unwrap();
+    big_features.features = "lookup kern1 {\n    pos A V -40;\n} kern1;\n".repeat(100_000);
+    let big_features_dir = tempdir().unwrap();
+    big_features.save(big_features_dir.path()).unwrap();
+    c.bench_function("read font with large features.fea", |b| {
+        b.iter(|| Font::load(big_features_dir.path()).expect("font should load"));
+    });
+    c.bench_function("read font with large features.fea lazily", |b| {
+        b.iter(|| {
+            let request = DataRequest::default().lazy_features(true);
+            Font::load_requested_data(big_features_dir.path(), request).expect("font should load")
+        });
+    });
 }
 
 criterion_group!(benches, criterion_benchmark);
@@ -1,6 +1,11 @@
 //! Load only requested font data.
 
 use std::path::Path;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::error::DataRequestParseError;
 
 /// A type that describes which components of a UFO should be loaded.
 ///
@@ -35,6 +40,27 @@ use std::path::Path;
 /// let datareq = DataRequest::none().lib(true);
 /// ```
 ///
+/// # Saving a request
+///
+/// A request can be parsed from a comma-separated list of the fields to load,
+/// which is convenient for command line flags:
+///
+/// ```
+/// use norad::DataRequest;
+///
+/// let datareq: DataRequest = "default_layer, kerning".parse().unwrap();
+/// assert!(datareq.kerning && !datareq.groups);
+/// ```
+///
+/// The accepted names are `layers`, `default_layer`, `lib`, `groups`, `kerning`,
+/// `features`, `lazy_features`, `data`, `images` and `lenient`, matching the
+/// builder methods.
+///
+/// A request also implements [`Serialize`] and [`Deserialize`], as a map from
+/// the same names to booleans, so it can be stored in a configuration file.
+/// Missing fields take their [default](DataRequest::default) value. A request
+/// using [`filter_layers`](Self::filter_layers) cannot be serialized.
+///
 /// [`Ufo::with_fields`]: struct.Ufo.html#method.with_fields
 #[derive(Debug)]
 #[non_exhaustive]
@@ -49,10 +75,18 @@ pub struct DataRequest<'a> {
     pub kerning: bool,
     /// Load Adobe .fea format feature file data
     pub features: bool,
+    /// Defer reading the feature file until it is accessed.
+    ///
+    /// See [`DataRequest::lazy_features`] for details.
+    pub lazy_features: bool,
     /// Load data
     pub data: bool,
     /// Load images
     pub images: bool,
+    /// Skip unknown elements in glyph outlines instead of returning an error.
+    ///
+    /// See [`DataRequest::lenient`] for details.
+    pub lenient: bool,
 }
 
 type FilterFn<'a> = dyn Fn(&str, &Path) -> bool + 'a;
@@ -89,8 +123,10 @@ impl<'a> DataRequest<'a> {
             groups: b,
             kerning: b,
             features: b,
+            lazy_features: false,
             data: b,
             images: b,
+            lenient: false,
         }
     }
 
@@ -180,6 +216,30 @@ impl<'a> DataRequest<'a> {
         self
     }
 
+    /// Request that the feature file be read on first access, rather than at load time.
+    ///
+    /// This only has an effect if [`features`](Self::features) are requested. The
+    /// font will remember the location of its `features.fea` file, and
+    /// [`Font::features`] will be empty until [`Font::features_mut`] is called.
+    /// Saving the font reads the file if it hasn't been read yet (and the
+    /// features haven't been replaced), so no features are lost. This can speed
+    /// up loading fonts with very large feature files when the features aren't
+    /// always needed.
+    ///
+    /// Only the path of the file is kept; it is not opened or mapped into memory
+    /// until it is read, so it must not be modified or removed while the font is
+    /// in use.
+    /// UFO v1 fonts, whose features may need upconverting, are always loaded eagerly.
+    ///
+    /// This option is not affected by [`DataRequest::all`] or [`DataRequest::none`].
+    ///
+    /// [`Font::features`]: crate::Font::features
+    /// [`Font::features_mut`]: crate::Font::features_mut
+    pub fn lazy_features(mut self, b: bool) -> Self {
+        self.lazy_features = b;
+        self
+    }
+
     /// Request that returned UFO data include data.
     pub fn data(mut self, b: bool) -> Self {
         self.data = b;
@@ -191,6 +251,160 @@ impl<'a> DataRequest<'a> {
         self.images = b;
         self
     }
+
+    /// Request that glyphs be parsed leniently.
+    ///
+    /// By default, glyph parsing is strict: an unknown element inside a glif's
+    /// `<outline>` (for instance from a future version of the spec, or from
+    /// a buggy tool) causes loading to fail with
+    /// [`GlifLoadError::UnexpectedOutlineElement`], which reports the element
+    /// name and its position in the file.
+    ///
+    /// In lenient mode, such elements (including any children they have) are
+    /// ignored, and the rest of the outline is loaded as normal. The ignored
+    /// elements are kept with the glyph and written back at the end of its
+    /// `<outline>` when the font is saved again.
+    ///
+    /// Similarly, an `<advance>` whose width or height is NaN or infinite is
+    /// rejected with [`ErrorKind::BadNumber`] by default, while in
+    /// lenient mode the offending value is replaced with 0. Replacements are
+    /// reported as warnings by [`Font::load_with_warnings`].
+    ///
+    /// Invalid identifiers (for instance empty ones) are rejected with
+    /// [`ErrorKind::BadIdentifier`] by default, while in lenient mode they are
+    /// dropped and the point, contour, component, anchor or guideline carrying
+    /// them is kept.
+    ///
+    /// This option is not affected by [`DataRequest::all`] or [`DataRequest::none`],
+    /// which always request strict parsing.
+    ///
+    /// [`GlifLoadError::UnexpectedOutlineElement`]: crate::error::GlifLoadError::UnexpectedOutlineElement
+    /// [`Font::load_with_warnings`]: crate::Font::load_with_warnings
+    /// [`ErrorKind::BadNumber`]: crate::error::ErrorKind::BadNumber
+    /// [`ErrorKind::BadIdentifier`]: crate::error::ErrorKind::BadIdentifier
+    pub fn lenient(mut self, b: bool) -> Self {
+        self.lenient = b;
+        self
+    }
+}
+
+/// The names accepted by `DataRequest::from_str`.
+pub(crate) static FIELD_NAMES: &[&str] = &[
+    "layers",
+    "default_layer",
+    "lib",
+    "groups",
+    "kerning",
+    "features",
+    "lazy_features",
+    "data",
+    "images",
+    "lenient",
+];
+
+impl FromStr for DataRequest<'_> {
+    type Err = DataRequestParseError;
+
+    /// Parses a comma-separated list of field names into a request that loads
+    /// only those fields.
+    ///
+    /// The order of the names doesn't matter; if both `layers` and
+    /// `default_layer` are given, all layers are loaded.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut profile = DataRequestProfile::from(&DataRequest::none());
+        for name in s.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+            let field = match name {
+                "layers" => &mut profile.layers,
+                "default_layer" => &mut profile.default_layer,
+                "lib" => &mut profile.lib,
+                "groups" => &mut profile.groups,
+                "kerning" => &mut profile.kerning,
+                "features" => &mut profile.features,
+                "lazy_features" => &mut profile.lazy_features,
+                "data" => &mut profile.data,
+                "images" => &mut profile.images,
+                "lenient" => &mut profile.lenient,
+                _ => return Err(DataRequestParseError { name: name.into() }),
+            };
+            *field = true;
+        }
+        Ok(profile.into())
+    }
+}
+
+/// The serialized form of a [`DataRequest`].
+#[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct DataRequestProfile {
+    layers: bool,
+    default_layer: bool,
+    lib: bool,
+    groups: bool,
+    kerning: bool,
+    features: bool,
+    lazy_features: bool,
+    data: bool,
+    images: bool,
+    lenient: bool,
+}
+
+impl Default for DataRequestProfile {
+    fn default() -> Self {
+        DataRequestProfile::from(&DataRequest::default())
+    }
+}
+
+impl From<&DataRequest<'_>> for DataRequestProfile {
+    /// Ignores the layer filter closure, if any.
+    fn from(request: &DataRequest) -> Self {
+        DataRequestProfile {
+            layers: request.layers.all,
+            default_layer: request.layers.load_default,
+            lib: request.lib,
+            groups: request.groups,
+            kerning: request.kerning,
+            features: request.features,
+            lazy_features: request.lazy_features,
+            data: request.data,
+            images: request.images,
+            lenient: request.lenient,
+        }
+    }
+}
+
+impl Serialize for DataRequest<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.layers.custom.is_some() {
+            return Err(serde::ser::Error::custom("a layer filter closure cannot be serialized"));
+        }
+        DataRequestProfile::from(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for DataRequest<'_> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        DataRequestProfile::deserialize(deserializer).map(Into::into)
+    }
+}
+
+impl From<DataRequestProfile> for DataRequest<'_> {
+    fn from(profile: DataRequestProfile) -> Self {
+        DataRequest {
+            layers: LayerFilter {
+                all: profile.layers,
+                load_default: profile.default_layer,
+                custom: None,
+            },
+            lib: profile.lib,
+            groups: profile.groups,
+            kerning: profile.kerning,
+            features: profile.features,
+            lazy_features: profile.lazy_features,
+            data: profile.data,
+            images: profile.images,
+            lenient: profile.lenient,
+        }
+    }
 }
 
 impl Default for DataRequest<'_> {
@@ -262,4 +476,64 @@ mod tests {
 
         assert!(all_fields_are_false(&dr));
     }
+
+    #[test]
+    fn test_datarequest_from_str() {
+        let dr: DataRequest = "".parse().unwrap();
+        assert!(all_fields_are_false(&dr));
+
+        let dr: DataRequest = "layers,lib,groups,kerning,features,data,images".parse().unwrap();
+        assert!(all_fields_are_true(&dr));
+
+        let dr: DataRequest = " default_layer , kerning,lenient ".parse().unwrap();
+        assert!(!dr.layers.all && dr.layers.load_default);
+        assert!(dr.kerning && dr.lenient && !dr.groups && !dr.lib);
+
+        // the order of the names doesn't matter
+        for names in ["layers,default_layer", "default_layer,layers"] {
+            let dr: DataRequest = names.parse().unwrap();
+            assert!(dr.layers.all && dr.layers.load_default, "{names}");
+        }
+
+        let err = "glyphs,kerning".parse::<DataRequest>().unwrap_err();
+        assert_eq!(err.name, "glyphs");
+        assert_eq!(
+            err.to_string(),
+            "unknown data request field 'glyphs', expected one of: layers, default_layer, lib, \
+             groups, kerning, features, lazy_features, data, images, lenient"
+        );
+    }
+
+    #[test]
+    fn test_datarequest_serde() {
+        let dr = DataRequest::none().default_layer(true).kerning(true).lazy_features(true);
+        let value = plist::to_value(&dr).unwrap();
+        let dict = value.as_dictionary().unwrap();
+        assert_eq!(dict.len(), FIELD_NAMES.len());
+        assert!(FIELD_NAMES.iter().all(|name| dict.contains_key(name)));
+        assert_eq!(dict.get("default_layer"), Some(&plist::Value::Boolean(true)));
+        assert_eq!(dict.get("groups"), Some(&plist::Value::Boolean(false)));
+
+        let dr: DataRequest = plist::from_value(&value).unwrap();
+        assert!(!dr.layers.all && dr.layers.load_default);
+        assert!(dr.kerning && dr.lazy_features && !dr.groups && !dr.lenient);
+
+        // missing fields take their default values
+        let mut dict = plist::Dictionary::new();
+        dict.insert("images".into(), false.into());
+        let dr: DataRequest = plist::from_value(&dict.into()).unwrap();
+        assert!(dr.layers.all && dr.lib && dr.kerning && !dr.images && !dr.lenient);
+
+        // deserializing is the inverse of serializing, even with both layer options
+        let dr = DataRequest::none().default_layer(true).layers(true);
+        let value = plist::to_value(&dr).unwrap();
+        let dr: DataRequest = plist::from_value(&value).unwrap();
+        assert!(dr.layers.all && dr.layers.load_default);
+        assert_eq!(plist::to_value(&dr).unwrap(), value);
+        let dr: DataRequest = "default_layer,layers".parse().unwrap();
+        assert_eq!(plist::to_value(&dr).unwrap(), value);
+
+        let dr = DataRequest::none().filter_layers(|name, _| name == "background");
+        assert!(plist::to_value(&dr).is_err());
+    }
 }
@@ -230,7 +230,7 @@ impl DataType for Image {
         if path.is_absolute() {
             return Err(StoreError::PathIsAbsolute);
         }
-        if path.parent().map_or(false, |p| !p.as_os_str().is_empty()) {
+        if path.parent().is_some_and(|p| !p.as_os_str().is_empty()) {
             return Err(StoreError::Subdir);
         }
         // Check for a valid PNG header signature.
@@ -278,10 +278,7 @@ impl<T: DataType> Store<T> {
 
     /// Returns a reference to the data corresponding to the path.
     pub fn get(&self, path: &Path) -> Option<Result<Arc<[u8]>, StoreError>> {
-        let cell = match self.items.get(path) {
-            Some(item) => item,
-            None => return None,
-        };
+        let cell = self.items.get(path)?;
 
         // If item isn't loaded, try to load it, saving the data or the error
         // NOTE: Figure out whether the item is unloaded and immediately drop the
@@ -298,6 +295,18 @@ impl<T: DataType> Store<T> {
         }
     }
 
+    /// Loads every item that hasn't been loaded yet, so that the store no
+    /// longer depends on the UFO it was read from.
+    #[cfg(feature = "tar")]
+    pub(crate) fn load_all(&self) -> Result<(), StoreEntryError> {
+        for path in self.keys() {
+            if let Some(Err(source)) = self.get(path) {
+                return Err(StoreEntryError::new(path.clone(), source));
+            }
+        }
+        Ok(())
+    }
+
     fn load_item(
         impl_type: &T,
         ufo_root: &Path,
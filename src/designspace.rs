@@ -9,8 +9,12 @@ use plist::Dictionary;
 
 use crate::error::{DesignSpaceLoadError, DesignSpaceSaveError};
 use crate::serde_xml_plist as serde_plist;
+use crate::shared_types::{lib_string_list, set_lib_string_list, PUBLIC_SKIP_EXPORT_GLYPHS_KEY};
 use crate::Name;
 
+/// The lib key holding the feature tags fontTools' varLib registers rules under.
+static FEATURE_VARS_FEATURE_TAG_KEY: &str = "com.github.fonttools.varLib.featureVarsFeatureTag";
+
 /// A [designspace].
 ///
 /// [designspace]: https://fonttools.readthedocs.io/en/latest/designspaceLib/index.html
@@ -257,14 +261,347 @@ impl DesignSpaceDocument {
 
     /// Save a designspace.
     pub fn save(&self, path: impl AsRef<Path>) -> Result<(), DesignSpaceSaveError> {
+        self.save_with_options(path, &DesignSpaceWriteOptions::default())
+    }
+
+    /// Save a designspace, using the provided [`DesignSpaceWriteOptions`].
+    ///
+    /// The document itself is left unchanged, even if the options reorder
+    /// what is written.
+    pub fn save_with_options(
+        &self,
+        path: impl AsRef<Path>,
+        options: &DesignSpaceWriteOptions,
+    ) -> Result<(), DesignSpaceSaveError> {
         let mut buf = String::from("<?xml version='1.0' encoding='UTF-8'?>\n");
         let mut xml_writer = quick_xml::se::Serializer::new(&mut buf);
         xml_writer.indent(' ', 2);
-        self.serialize(xml_writer)?;
+        if options.sort_sources {
+            let mut sorted = self.clone();
+            sorted.sort_sources();
+            sorted.serialize(xml_writer)?;
+        } else {
+            self.serialize(xml_writer)?;
+        }
         buf.push('\n'); // trailing newline
         close_already::fs::write(path, buf)?;
         Ok(())
     }
+
+    /// Returns the first source named `name`, if any.
+    pub fn find_source_by_name(&self, name: &str) -> Option<&Source> {
+        self.sources.iter().find(|source| source.name.as_deref() == Some(name))
+    }
+
+    /// Returns the first instance named `name`, if any.
+    pub fn find_instance_by_name(&self, name: &str) -> Option<&Instance> {
+        self.instances.iter().find(|instance| instance.name.as_deref() == Some(name))
+    }
+
+    /// Removes and returns the source at `index`, or `None` if it is out of bounds.
+    ///
+    /// The order of the remaining sources is preserved, so the sources after
+    /// `index` each move down by one.
+    pub fn remove_source(&mut self, index: usize) -> Option<Source> {
+        (index < self.sources.len()).then(|| self.sources.remove(index))
+    }
+
+    /// Removes and returns the instance at `index`, or `None` if it is out of bounds.
+    ///
+    /// The order of the remaining instances is preserved, so the instances after
+    /// `index` each move down by one.
+    pub fn remove_instance(&mut self, index: usize) -> Option<Instance> {
+        (index < self.instances.len()).then(|| self.instances.remove(index))
+    }
+
+    /// Sorts the sources into a canonical order: the default source first,
+    /// followed by the others ordered by their location.
+    ///
+    /// A source is a default source if it sits at the default of every axis.
+    /// Locations are compared coordinate by coordinate, in the order of the
+    /// document's axes, with a dimension missing from a location taken to be
+    /// at the axis default. A dimension with only a user space value is
+    /// converted to design space with the axis map. Sources at the same location are ordered with
+    /// those using the default layer first, and otherwise keep their relative
+    /// order.
+    ///
+    /// Some tools give meaning to the order of sources, so this is never done
+    /// implicitly; see [`DesignSpaceWriteOptions::sort_sources`] to sort on save.
+    pub fn sort_sources(&mut self) {
+        let defaults: Vec<_> = self.axes.iter().map(|axis| axis.design_extents().1).collect();
+        let coordinates = |source: &Source| -> Vec<f32> {
+            self.axes
+                .iter()
+                .zip(&defaults)
+                .map(|(axis, default)| {
+                    source
+                        .location
+                        .iter()
+                        .find(|dim| dim.name == axis.name)
+                        .and_then(|dim| {
+                            dim.xvalue.or_else(|| dim.uservalue.map(|v| axis.map_to_design(v)))
+                        })
+                        .unwrap_or(*default)
+                })
+                .collect()
+        };
+        let mut keyed: Vec<_> = std::mem::take(&mut self.sources)
+            .into_iter()
+            .map(|source| {
+                let coords = coordinates(&source);
+                let is_default = coords == defaults;
+                (!is_default, coords, source.layer.is_some(), source)
+            })
+            .collect();
+        keyed.sort_by(|a, b| {
+            a.0.cmp(&b.0)
+                .then_with(|| {
+                    a.1.iter()
+                        .zip(&b.1)
+                        .map(|(a, b)| a.total_cmp(b))
+                        .find(|ord| ord.is_ne())
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                })
+                .then(a.2.cmp(&b.2))
+        });
+        self.sources = keyed.into_iter().map(|(_, _, _, source)| source).collect();
+    }
+
+    /// Returns the names of the glyphs that should not be exported from any of
+    /// the fonts built from this designspace, stored in the lib's
+    /// `public.skipExportGlyphs` key.
+    ///
+    /// This list takes precedence over the lists in the sources' own libs.
+    pub fn skip_export_glyphs(&self) -> Vec<String> {
+        lib_string_list(&self.lib, PUBLIC_SKIP_EXPORT_GLYPHS_KEY)
+    }
+
+    /// Sets the glyphs that should not be exported, stored in the lib's
+    /// `public.skipExportGlyphs` key.
+    ///
+    /// An empty list removes the key.
+    pub fn set_skip_export_glyphs(&mut self, names: Vec<String>) {
+        set_lib_string_list(&mut self.lib, PUBLIC_SKIP_EXPORT_GLYPHS_KEY, names);
+    }
+
+    /// Returns the OpenType feature tags that the [rules] are registered
+    /// under when building a variable font, stored in the lib's
+    /// `com.github.fonttools.varLib.featureVarsFeatureTag` key.
+    ///
+    /// fontTools stores the tags as a single comma-separated string. An empty
+    /// list means the key is unset, in which case `rvrn` is used, or `rclt` if
+    /// the rules are [processed last](RuleProcessing::Last).
+    ///
+    /// [rules]: DesignSpaceDocument::rules
+    pub fn feature_vars_feature_tags(&self) -> Vec<String> {
+        self.lib
+            .get(FEATURE_VARS_FEATURE_TAG_KEY)
+            .and_then(plist::Value::as_string)
+            .into_iter()
+            .flat_map(|tags| tags.split(','))
+            .map(str::trim)
+            .filter(|tag| !tag.is_empty())
+            .map(String::from)
+            .collect()
+    }
+
+    /// Sets the OpenType feature tags that the rules are registered under,
+    /// stored in the lib's `com.github.fonttools.varLib.featureVarsFeatureTag` key.
+    ///
+    /// An empty list removes the key.
+    pub fn set_feature_vars_feature_tags(&mut self, tags: Vec<String>) {
+        if tags.is_empty() {
+            self.lib.remove(FEATURE_VARS_FEATURE_TAG_KEY);
+        } else {
+            self.lib.insert(FEATURE_VARS_FEATURE_TAG_KEY.into(), tags.join(",").into());
+        }
+    }
+
+    /// Fills in [`Instance::filename`] for every instance that lacks one.
+    ///
+    /// The file name is generated from `template`, in which the placeholders
+    /// `{familyname}` and `{stylename}` are replaced with the instance's names,
+    /// and is placed in `output_dir` (relative to the document; may be empty).
+    /// For example, the template `"{familyname}-{stylename}.ufo"` and output dir
+    /// `"instances"` would give `instances/MyFamily-Bold.ufo`.
+    ///
+    /// The substituted names are sanitized so that they form a single path
+    /// component: whitespace is removed, and path separators, control characters
+    /// and characters that are not allowed in file names on common platforms
+    /// are replaced with underscores, as are the dots of a name made only of
+    /// dots (such as `..`). Placeholder text inside a name is not substituted.
+    /// Backslashes in `output_dir` are treated as path separators, and are
+    /// written as forward slashes.
+    ///
+    /// Instances missing a name that the template uses are left untouched.
+    /// Returns the number of instances that were given a file name.
+    pub fn fill_instance_filenames(&mut self, template: &str, output_dir: &str) -> usize {
+        let output_dir = output_dir.replace('\\', "/");
+        let output_dir = output_dir.trim_end_matches('/');
+        let mut filled = 0;
+        for instance in self.instances.iter_mut().filter(|i| i.filename.is_none()) {
+            let Some(file_name) = expand_file_name_template(template, instance) else {
+                continue;
+            };
+            instance.filename = Some(if output_dir.is_empty() {
+                file_name
+            } else {
+                format!("{output_dir}/{file_name}")
+            });
+            filled += 1;
+        }
+        filled
+    }
+}
+
+/// Substitutes the instance's names into `template`, in a single pass so
+/// that placeholder text inside a name is left alone.
+///
+/// Returns `None` if the template uses a name that the instance lacks.
+fn expand_file_name_template(template: &str, instance: &Instance) -> Option<String> {
+    let mut file_name = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        file_name.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let value = if let Some(after) = rest.strip_prefix("{familyname}") {
+            rest = after;
+            &instance.familyname
+        } else if let Some(after) = rest.strip_prefix("{stylename}") {
+            rest = after;
+            &instance.stylename
+        } else {
+            file_name.push('{');
+            rest = &rest[1..];
+            continue;
+        };
+        file_name.push_str(&sanitize_file_name_part(value.as_deref()?));
+    }
+    file_name.push_str(rest);
+    Some(escape_dot_names(file_name))
+}
+
+/// Options for writing a [`DesignSpaceDocument`].
+///
+/// # Example
+///
+/// ```
+/// use norad::designspace::DesignSpaceWriteOptions;
+/// let options = DesignSpaceWriteOptions::new().sort_sources(true);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DesignSpaceWriteOptions {
+    sort_sources: bool,
+}
+
+impl DesignSpaceWriteOptions {
+    /// Create new, default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder-style method to write the sources in the canonical order of
+    /// [`DesignSpaceDocument::sort_sources`], rather than in document order.
+    ///
+    /// Off by default, as some tools rely on the order of sources.
+    pub fn sort_sources(mut self, sort: bool) -> Self {
+        self.sort_sources = sort;
+        self
+    }
+}
+
+/// Makes `name` safe to use as (part of) a single path component.
+fn sanitize_file_name_part(name: &str) -> String {
+    let name = name
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    escape_dot_names(name)
+}
+
+/// Replaces the dots of a name made only of dots, such as `.` or `..`, with
+/// underscores, so that it cannot refer to the current or parent directory.
+fn escape_dot_names(name: String) -> String {
+    if !name.is_empty() && name.chars().all(|c| c == '.') {
+        "_".repeat(name.len())
+    } else {
+        name
+    }
+}
+
+impl Axis {
+    /// Maps a design space value on this axis to a normalized coordinate in `[-1, 1]`.
+    ///
+    /// The axis minimum maps to -1, the default to 0 and the maximum to 1, with
+    /// linear interpolation in between. Values outside the axis range are clamped.
+    /// If the axis has a [`map`](Axis::map), the minimum, default and maximum are
+    /// first converted to design space coordinates.
+    ///
+    /// Normalization is not meaningful for discrete axes; for those, the smallest
+    /// and largest of the [`values`](Axis::values) are used as the axis extremes.
+    pub fn normalize(&self, design_value: f32) -> f32 {
+        let (min, default, max) = self.design_extents();
+        let value = design_value.max(min).min(max);
+        if value < default {
+            (value - default) / (default - min)
+        } else if value > default {
+            (value - default) / (max - default)
+        } else {
+            0.0
+        }
+    }
+
+    /// Maps a normalized coordinate in `[-1, 1]` back to a design space value.
+    ///
+    /// This is the inverse of [`Axis::normalize`]; values outside `[-1, 1]` are clamped.
+    pub fn denormalize(&self, normalized: f32) -> f32 {
+        let (min, default, max) = self.design_extents();
+        let value = normalized.clamp(-1.0, 1.0);
+        if value < 0.0 {
+            default + value * (default - min)
+        } else {
+            default + value * (max - default)
+        }
+    }
+
+    /// Returns the minimum, default and maximum of the axis in design space coordinates.
+    fn design_extents(&self) -> (f32, f32, f32) {
+        let (min, max) = match (self.minimum, self.maximum) {
+            (Some(min), Some(max)) => (min, max),
+            _ => {
+                let values = self.values.as_deref().unwrap_or_default();
+                let min = values.iter().copied().fold(self.default, f32::min);
+                let max = values.iter().copied().fold(self.default, f32::max);
+                (min, max)
+            }
+        };
+        (self.map_to_design(min), self.map_to_design(self.default), self.map_to_design(max))
+    }
+
+    /// Converts a user space value to design space, using the axis map if present.
+    fn map_to_design(&self, user_value: f32) -> f32 {
+        let mut map = match self.map.as_deref() {
+            Some(map) if !map.is_empty() => map.to_vec(),
+            _ => return user_value,
+        };
+        map.sort_by(|a, b| a.input.total_cmp(&b.input));
+        let first = &map[0];
+        let last = &map[map.len() - 1];
+        if user_value <= first.input {
+            return user_value + first.output - first.input;
+        }
+        if user_value >= last.input {
+            return user_value + last.output - last.input;
+        }
+        let idx = map.iter().position(|m| m.input > user_value).unwrap();
+        let (lo, hi) = (&map[idx - 1], &map[idx]);
+        lo.output + (user_value - lo.input) * (hi.output - lo.output) / (hi.input - lo.input)
+    }
 }
 
 impl Rules {
@@ -418,6 +755,56 @@ mod tests {
         );
     }
 
+    #[test]
+    fn sort_sources() {
+        let mut ds = DesignSpaceDocument::load("testdata/MutatorSans.designspace").unwrap();
+        let mut background = ds.sources[0].clone();
+        background.layer = Some("background".into());
+        ds.sources.reverse();
+        ds.sources.insert(0, background);
+        // a dimension missing from the location is at the axis default, which
+        // puts support.S.middle at the same location as support.crossbar
+        assert_eq!(ds.sources[1].layer.as_deref(), Some("support.S.middle"));
+        ds.sources[1].location.retain(|dim| dim.name != "width");
+        // a user space value is mapped to design space; width has no map, so
+        // this moves BoldWide to where LightWide is, rather than to the default
+        assert_eq!(ds.sources[4].stylename.as_deref(), Some("BoldWide"));
+        ds.sources[4].location = vec![
+            Dimension { name: "width".into(), uservalue: Some(1000.), xvalue: None, yvalue: None },
+            Dimension { name: "weight".into(), uservalue: Some(0.), xvalue: None, yvalue: None },
+        ];
+
+        let unsorted = ds.clone();
+        ds.sort_sources();
+        let order: Vec<_> = ds
+            .sources
+            .iter()
+            .map(|s| (s.stylename.as_deref().unwrap(), s.layer.as_deref()))
+            .collect();
+        assert_eq!(
+            order,
+            [
+                ("LightCondensed", None),
+                ("LightCondensed", Some("background")),
+                ("LightCondensed", Some("support.S.middle")),
+                ("LightCondensed", Some("support.crossbar")),
+                ("BoldCondensed", None),
+                ("BoldWide", None),
+                ("LightWide", None),
+                ("LightCondensed", Some("support.S.wide")),
+            ]
+        );
+
+        // sorting is only done on save when asked for
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("MutatorSans.designspace");
+        unsorted.save(&path).unwrap();
+        assert_eq!(DesignSpaceDocument::load(&path).unwrap().sources, unsorted.sources);
+        let options = DesignSpaceWriteOptions::new().sort_sources(true);
+        unsorted.save_with_options(&path, &options).unwrap();
+        assert_eq!(DesignSpaceDocument::load(&path).unwrap().sources, ds.sources);
+    }
+
     // <https://github.com/linebender/norad/issues/300>
     #[test]
     fn load_with_no_instances() {
@@ -468,6 +855,50 @@ mod tests {
         assert!(!serialized.contains("<lib/>"));
     }
 
+    #[test]
+    fn lib_helpers_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("lib.designspace");
+
+        let mut ds = DesignSpaceDocument::load("testdata/wght.designspace").unwrap();
+        assert!(ds.skip_export_glyphs().is_empty());
+        assert!(ds.feature_vars_feature_tags().is_empty());
+        ds.set_skip_export_glyphs(vec!["b".into(), "a.alt".into()]);
+        ds.set_feature_vars_feature_tags(vec!["rvrn".into(), "calt".into()]);
+        ds.lib.insert("com.example.buildVariable".into(), Value::Boolean(true));
+        assert_eq!(
+            ds.lib.get(FEATURE_VARS_FEATURE_TAG_KEY),
+            Some(&Value::String("rvrn,calt".into()))
+        );
+        assert_eq!(
+            ds.lib.keys().collect::<Vec<_>>(),
+            [
+                "org.linebender.hasLoadedLibCorrectly",
+                "com.github.googlei18n.ufo2ft.featureWriters",
+                "public.skipExportGlyphs",
+                "com.github.fonttools.varLib.featureVarsFeatureTag",
+                "com.example.buildVariable"
+            ]
+        );
+
+        ds.save(&path).unwrap();
+        let mut loaded = DesignSpaceDocument::load(&path).unwrap();
+        assert_eq!(loaded, ds);
+        assert_eq!(loaded.skip_export_glyphs(), ["b", "a.alt"]);
+        assert_eq!(loaded.feature_vars_feature_tags(), ["rvrn", "calt"]);
+
+        loaded.set_skip_export_glyphs(Vec::new());
+        loaded.set_feature_vars_feature_tags(Vec::new());
+        assert!(!loaded.lib.contains_key(FEATURE_VARS_FEATURE_TAG_KEY));
+        assert_eq!(loaded.lib.remove("com.example.buildVariable"), Some(Value::Boolean(true)));
+        loaded.lib.clear();
+
+        loaded.save(&path).unwrap();
+        let saved = std::fs::read_to_string(&path).unwrap();
+        // instances still have libs of their own, one level further in
+        assert!(!saved.contains("\n  <lib"), "{saved}");
+    }
+
     #[test]
     fn load_save_round_trip() {
         // Given
@@ -543,6 +974,167 @@ mod tests {
         assert_eq!(ds_initial, ds_after);
     }
 
+    #[test]
+    fn axis_normalize() {
+        let axis = Axis {
+            name: "Weight".into(),
+            tag: "wght".into(),
+            default: 400.,
+            minimum: Some(100.),
+            maximum: Some(900.),
+            ..Default::default()
+        };
+        assert_eq!(axis.normalize(100.), -1.);
+        assert_eq!(axis.normalize(250.), -0.5);
+        assert_eq!(axis.normalize(400.), 0.);
+        assert_eq!(axis.normalize(650.), 0.5);
+        assert_eq!(axis.normalize(900.), 1.);
+        assert_eq!(axis.normalize(1000.), 1.);
+        assert_eq!(axis.normalize(0.), -1.);
+
+        assert_eq!(axis.denormalize(-1.), 100.);
+        assert_eq!(axis.denormalize(-0.5), 250.);
+        assert_eq!(axis.denormalize(0.), 400.);
+        assert_eq!(axis.denormalize(0.5), 650.);
+        assert_eq!(axis.denormalize(1.), 900.);
+    }
+
+    #[test]
+    fn axis_normalize_mapped() {
+        let axis = Axis {
+            name: "Weight".into(),
+            tag: "wght".into(),
+            default: 400.,
+            minimum: Some(100.),
+            maximum: Some(900.),
+            map: Some(vec![
+                AxisMapping { input: 100., output: 20. },
+                AxisMapping { input: 400., output: 80. },
+                AxisMapping { input: 900., output: 200. },
+            ]),
+            ..Default::default()
+        };
+        assert_eq!(axis.normalize(20.), -1.);
+        assert_eq!(axis.normalize(50.), -0.5);
+        assert_eq!(axis.normalize(80.), 0.);
+        assert_eq!(axis.normalize(140.), 0.5);
+        assert_eq!(axis.normalize(200.), 1.);
+        assert_eq!(axis.denormalize(0.5), 140.);
+    }
+
+    #[test]
+    fn fill_instance_filenames() {
+        let instance = |family: Option<&str>, style: Option<&str>| Instance {
+            familyname: family.map(Into::into),
+            stylename: style.map(Into::into),
+            ..Default::default()
+        };
+        let mut ds = DesignSpaceDocument {
+            instances: vec![
+                instance(Some("My Family"), Some("Bold")),
+                instance(Some("Odd/Family:Name"), Some("Semi Bold Italic?")),
+                instance(None, Some("Regular")),
+                Instance { filename: Some("keep.ufo".into()), ..instance(Some("A"), Some("B")) },
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(ds.fill_instance_filenames("{familyname}-{stylename}.ufo", "instances/"), 2);
+        let filenames: Vec<_> = ds.instances.iter().map(|i| i.filename.as_deref()).collect();
+        assert_eq!(
+            filenames,
+            [
+                Some("instances/MyFamily-Bold.ufo"),
+                Some("instances/Odd_Family_Name-SemiBoldItalic_.ufo"),
+                None,
+                Some("keep.ufo"),
+            ]
+        );
+
+        assert_eq!(ds.fill_instance_filenames("{stylename}.ufo", ""), 1);
+        assert_eq!(ds.instances[2].filename.as_deref(), Some("Regular.ufo"));
+    }
+
+    #[test]
+    fn fill_instance_filenames_escapes_dot_names() {
+        let instance =
+            |style: &str| Instance { stylename: Some(style.into()), ..Default::default() };
+        let mut ds = DesignSpaceDocument {
+            instances: vec![instance(".."), instance("."), instance(".Hidden")],
+            ..Default::default()
+        };
+        assert_eq!(ds.fill_instance_filenames("{stylename}", "out"), 3);
+        let filenames: Vec<_> = ds.instances.iter().map(|i| i.filename.as_deref()).collect();
+        assert_eq!(filenames, [Some("out/__"), Some("out/_"), Some("out/.Hidden")]);
+
+        // names that only form a dot name once combined
+        let mut ds = DesignSpaceDocument {
+            instances: vec![Instance {
+                familyname: Some(".".into()),
+                stylename: Some(".".into()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        ds.fill_instance_filenames("{familyname}{stylename}", "");
+        assert_eq!(ds.instances[0].filename.as_deref(), Some("__"));
+    }
+
+    #[test]
+    fn fill_instance_filenames_substitutes_once() {
+        let mut ds = DesignSpaceDocument {
+            instances: vec![Instance {
+                familyname: Some("{stylename}".into()),
+                stylename: Some("Bold{familyname}".into()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        assert_eq!(ds.fill_instance_filenames("{familyname}-{stylename}-{other}.ufo", ""), 1);
+        assert_eq!(
+            ds.instances[0].filename.as_deref(),
+            Some("{stylename}-Bold{familyname}-{other}.ufo")
+        );
+    }
+
+    #[test]
+    fn fill_instance_filenames_normalizes_output_dir() {
+        let instance = Instance { stylename: Some("Bold".into()), ..Default::default() };
+        let mut ds =
+            DesignSpaceDocument { instances: vec![instance.clone()], ..Default::default() };
+        ds.fill_instance_filenames("{stylename}.ufo", "build\\instances\\");
+        assert_eq!(ds.instances[0].filename.as_deref(), Some("build/instances/Bold.ufo"));
+
+        let mut ds = DesignSpaceDocument { instances: vec![instance], ..Default::default() };
+        ds.fill_instance_filenames("{stylename}.ufo", "instances\\/");
+        assert_eq!(ds.instances[0].filename.as_deref(), Some("instances/Bold.ufo"));
+    }
+
+    #[test]
+    fn find_and_remove_sources_and_instances() {
+        let mut ds = DesignSpaceDocument::load("testdata/wght.designspace").unwrap();
+        let bold = ds.find_source_by_name("Test Family Bold").unwrap();
+        assert_eq!(bold.filename, "TestFamily-Bold.ufo");
+        assert!(ds.find_source_by_name("Test Family Black").is_none());
+        let regular = ds.find_instance_by_name("Test Family Regular").unwrap();
+        assert_eq!(regular.stylename.as_deref(), Some("Regular"));
+
+        let instances = ds.instances.clone();
+        let removed = ds.remove_source(0).unwrap();
+        assert_eq!(removed.name.as_deref(), Some("Test Family Regular"));
+        assert_eq!(ds.sources.len(), 1);
+        assert_eq!(ds.sources[0].name.as_deref(), Some("Test Family Bold"));
+        assert!(ds.find_source_by_name("Test Family Regular").is_none());
+        assert_eq!(ds.instances, instances);
+        assert!(ds.remove_source(1).is_none());
+        assert_eq!(ds.sources.len(), 1);
+
+        let removed = ds.remove_instance(1).unwrap();
+        assert_eq!(removed.name.as_deref(), Some("Test Family Bold"));
+        assert_eq!(ds.instances, instances[..1]);
+        assert!(ds.remove_instance(1).is_none());
+    }
+
     #[test]
     fn accept_always_on_rules() {
         // Given
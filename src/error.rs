@@ -10,7 +10,7 @@ use thiserror::Error;
 
 pub use crate::shared_types::ColorError;
 use crate::write::CustomSerializationError;
-use crate::Name;
+use crate::{Identifier, Name};
 
 /// An error that occurs while attempting to read a designspace file from disk.
 #[derive(Debug, Error)]
@@ -72,6 +72,18 @@ pub enum GlifLoadError {
     /// The .glif file was malformed.
     #[error("failed to parse glyph data: {0}")]
     Parse(ErrorKind),
+    /// An unknown element was found inside the `<outline>` element.
+    ///
+    /// This is only returned when parsing strictly; see [`DataRequest::lenient`].
+    ///
+    /// [`DataRequest::lenient`]: crate::DataRequest::lenient
+    #[error("unexpected element <{element}> in <outline> at byte offset {position}")]
+    UnexpectedOutlineElement {
+        /// The name of the element.
+        element: String,
+        /// The byte offset into the file just after the element's start tag.
+        position: usize,
+    },
     /// The glyph lib's `public.objectLibs` value was something other than a dictionary.
     #[error("the glyph lib's 'public.objectLibs' value must be a dictionary")]
     PublicObjectLibsMustBeDictionary,
@@ -80,6 +92,40 @@ pub enum GlifLoadError {
     ObjectLibMustBeDictionary(String),
 }
 
+/// A problem in a .glif file that was tolerated because it was parsed leniently.
+///
+/// See [`DataRequest::lenient`] and [`Font::load_with_warnings`].
+///
+/// [`DataRequest::lenient`]: crate::DataRequest::lenient
+/// [`Font::load_with_warnings`]: crate::Font::load_with_warnings
+#[derive(Debug, Clone, PartialEq, Error)]
+#[non_exhaustive]
+pub enum GlifLoadWarning {
+    /// The `<advance>` element had a NaN or infinite value, which was replaced with 0.
+    #[error("advance {attribute} was {value}, replaced with 0")]
+    NonFiniteAdvance {
+        /// The name of the attribute, either `width` or `height`.
+        attribute: &'static str,
+        /// The value found in the file.
+        value: f64,
+    },
+    /// An identifier was not valid, and was dropped from the object carrying it.
+    #[error("invalid identifier '{0}' was dropped")]
+    InvalidIdentifier(String),
+}
+
+/// A [`GlifLoadWarning`], together with the glyph it was found in.
+#[derive(Debug, Clone, PartialEq, Error)]
+#[error("glyph '{glyph}' in layer '{layer}': {warning}")]
+pub struct LoadWarning {
+    /// The name of the layer containing the glyph.
+    pub layer: Name,
+    /// The name of the glyph.
+    pub glyph: Name,
+    /// The problem that was tolerated.
+    pub warning: GlifLoadWarning,
+}
+
 /// An error that occurs while attempting to read a UFO package from disk.
 #[derive(Debug, Error)]
 #[non_exhaustive]
@@ -138,6 +184,10 @@ pub enum FontLoadError {
         /// The underlying error.
         source: PlistError,
     },
+    /// Failed to read or unpack a tar archive.
+    #[cfg(feature = "tar")]
+    #[error("failed to unpack tar archive")]
+    TarArchive(#[source] IoError),
     /// Norad can currently only open UFO (directory) packages.
     #[error("only UFO (directory) packages are supported")]
     UfoNotADir,
@@ -347,6 +397,129 @@ pub enum GroupsValidationError {
     },
 }
 
+/// A problem with a glyph's data, as reported by [`Glyph::validate`].
+///
+/// [`Glyph::validate`]: crate::Glyph::validate
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum GlyphValidationError {
+    /// A component's base glyph does not exist in the glyph's layer.
+    #[error("component {index} refers to base glyph '{base}', which does not exist in the layer")]
+    MissingComponentBase {
+        /// The index of the component in the glyph's components.
+        index: usize,
+        /// The name of the missing base glyph.
+        base: Name,
+    },
+}
+
+/// A problem with a font's data, as reported by [`Font::validate`].
+///
+/// [`Font::validate`]: crate::Font::validate
+#[derive(Debug, Clone, PartialEq, Error)]
+#[non_exhaustive]
+pub enum FontValidationError {
+    /// A glyph is referenced but does not exist in the default layer.
+    #[error("glyph '{name}' referenced in {location} does not exist in the default layer")]
+    MissingGlyph {
+        /// The name of the missing glyph.
+        name: Name,
+        /// Where the glyph is referenced.
+        location: ReferenceLocation,
+    },
+    /// A kerning group is referenced but does not exist in the groups.
+    #[error("kerning group '{name}' referenced in {location} does not exist")]
+    MissingGroup {
+        /// The name of the missing group.
+        name: Name,
+        /// Where the group is referenced.
+        location: ReferenceLocation,
+    },
+    /// More than one glyph in the default layer is mapped to the same codepoint,
+    /// which makes the font's character map ambiguous.
+    #[error("codepoint U+{:04X} is assigned to multiple glyphs: {}", u32::from(*codepoint), DisplayNames(glyphs))]
+    DuplicateCodepoint {
+        /// The codepoint assigned more than once.
+        codepoint: char,
+        /// The names of the glyphs claiming the codepoint, in alphabetical order.
+        glyphs: Vec<Name>,
+    },
+    /// A problem with a glyph, found by [`Glyph::validate`].
+    ///
+    /// [`Glyph::validate`]: crate::Glyph::validate
+    #[error("glyph '{glyph}' in layer '{layer}' is invalid")]
+    Glyph {
+        /// The name of the layer containing the glyph.
+        layer: Name,
+        /// The name of the glyph.
+        glyph: Name,
+        /// The problem with the glyph.
+        #[source]
+        source: GlyphValidationError,
+    },
+}
+
+/// Formats a list of names as a comma-separated list of quoted names.
+struct DisplayNames<'a>(&'a [Name]);
+
+impl std::fmt::Display for DisplayNames<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, name) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "'{name}'")?;
+        }
+        Ok(())
+    }
+}
+
+/// Where a name is referenced, for reporting [`FontValidationError`]s.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ReferenceLocation {
+    /// The entry at `index` of the lib's `public.glyphOrder` list.
+    GlyphOrder {
+        /// The index into the list.
+        index: usize,
+    },
+    /// The members of a group.
+    Group {
+        /// The name of the group.
+        group: Name,
+    },
+    /// A kerning pair.
+    Kerning {
+        /// The first (left) side of the pair.
+        first: Name,
+        /// The second (right) side of the pair.
+        second: Name,
+    },
+}
+
+impl std::fmt::Display for ReferenceLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReferenceLocation::GlyphOrder { index } => {
+                write!(f, "public.glyphOrder (index {index})")
+            }
+            ReferenceLocation::Group { group } => write!(f, "group '{group}'"),
+            ReferenceLocation::Kerning { first, second } => {
+                write!(f, "kerning pair ('{first}', '{second}')")
+            }
+        }
+    }
+}
+
+/// An error returned when parsing a [`DataRequest`](crate::DataRequest) from a
+/// string that contains an unknown field name.
+#[derive(Debug, Error)]
+#[error("unknown data request field '{name}', expected one of: {}", crate::data_request::FIELD_NAMES.join(", "))]
+pub struct DataRequestParseError {
+    /// The unrecognized field name.
+    pub name: String,
+}
+
 /// An error returned when there is an inappropriate negative sign on a value.
 #[derive(Debug, Error)]
 #[error("expected a positive value")]
@@ -365,6 +538,58 @@ impl ConvertContourError {
     }
 }
 
+/// An error that occurs while decomposing the components of a glyph.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum DecomposeError {
+    /// A component's base glyph does not exist in the layer.
+    #[error("component base glyph '{0}' does not exist in the layer")]
+    MissingBase(Name),
+    /// Components reference each other in a cycle.
+    #[error("component cycle involving glyph '{0}'")]
+    Cycle(Name),
+}
+
+/// An error that occurs while drawing a glyph into a [`Pen`](crate::Pen).
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum DrawError {
+    /// A component could not be decomposed.
+    #[error(transparent)]
+    Component(#[from] DecomposeError),
+    /// A contour has an invalid sequence of point types.
+    #[error("glyph '{0}' has a contour with an invalid sequence of points")]
+    BadContour(Name),
+}
+
+/// An error that occurs while rebuilding a glyph from interpolatable coordinates.
+///
+/// See [`Glyph::from_interpolatable_coords`](crate::Glyph::from_interpolatable_coords).
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum InterpolatableCoordsError {
+    /// The number of coordinates does not match the template glyph.
+    #[error("expected {expected} coordinates for the template glyph, found {found}")]
+    WrongLength {
+        /// The number of coordinates the template glyph has.
+        expected: usize,
+        /// The number of coordinates that were given.
+        found: usize,
+    },
+}
+
+/// An error that occurs while opening a closed [`Contour`](crate::Contour).
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum OpenContourError {
+    /// The contour is already open.
+    #[error("the contour is already open")]
+    AlreadyOpen,
+    /// The point at the given index is an off-curve point.
+    #[error("cannot open a contour at off-curve point {0}")]
+    OffCurve(usize),
+}
+
 /// An error that occurs while attempting to write a UFO package to disk.
 #[derive(Debug, Error)]
 #[non_exhaustive]
@@ -440,6 +665,14 @@ pub enum FontWriteError {
     /// There exists a `public.objectLibs` lib key when it should be set only by norad.
     #[error("the `public.objectLibs` lib key is managed by norad and must not be set manually")]
     PreexistingPublicObjectLibsKey,
+    /// Failed to inspect the existing contents of the target directory.
+    #[error("failed to read existing contents of '{path}'")]
+    ReadExisting {
+        /// The path that could not be read.
+        path: PathBuf,
+        /// The underlying error.
+        source: IoError,
+    },
 }
 
 /// An error that occurs while attempting to read a UFO layer from disk.
@@ -481,6 +714,10 @@ pub enum GlifWriteError {
     /// be affected, although this is very unlikely.
     #[error("internal error while writing lib data, please open an issue")]
     InternalLibWriteError,
+    /// Several objects with a lib share an identifier, so their libs can't all
+    /// be stored in `public.objectLibs`.
+    #[error("more than one object with a lib has the identifier '{0}'")]
+    DuplicateObjectLibIdentifier(Identifier),
     /// Failed to write a .glif file to disk.
     #[error("failed to write .glif file")]
     Io(#[source] IoError),
@@ -596,7 +833,7 @@ impl std::fmt::Display for ErrorKind {
             BadGuideline => write!(f, "bad guideline"),
             BadComponent => write!(f, "bad component"),
             BadImage => write!(f, "bad image"),
-            BadIdentifier => write!(f, "an identifier must be at most 100 characters long and contain only ASCII characters in the range 0x20 to 0x7E"),
+            BadIdentifier => write!(f, "an identifier must be between 1 and 100 characters long and contain only ASCII characters in the range 0x20 to 0x7E"),
             InvalidName => write!(f, "name is empty or contains control characters"),
             BadLib => write!(f, "bad lib"),
             UnexpectedDuplicate => write!(f, "unexpected duplicate"),
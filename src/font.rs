@@ -2,21 +2,28 @@
 
 #![deny(rustdoc::broken_intra_doc_links)]
 
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 
 use crate::data_request::LayerFilter;
 use crate::datastore::{DataStore, ImageStore};
-use crate::error::{FontLoadError, FontWriteError};
+use crate::error::{
+    FontLoadError, FontValidationError, FontWriteError, LoadWarning, NamingError, ReferenceLocation,
+};
 use crate::fontinfo::FontInfo;
-use crate::glyph::Glyph;
+use crate::glyph::{CurveTypeSummary, Glyph};
 use crate::groups::{validate_groups, Groups};
 use crate::guideline::Guideline;
 use crate::kerning::Kerning;
 use crate::layer::{Layer, LayerContents, LAYER_CONTENTS_FILE};
 use crate::name::Name;
 use crate::names::NameList;
-use crate::shared_types::{Plist, PUBLIC_OBJECT_LIBS_KEY};
+use crate::shared_types::{
+    lib_string_list, set_lib_string_list, Plist, PUBLIC_GLYPH_ORDER_KEY, PUBLIC_OBJECT_LIBS_KEY,
+    PUBLIC_SKIP_EXPORT_GLYPHS_KEY,
+};
+use crate::spacing::{NegativeSidebearing, SpacingReport, WidthCount};
 use crate::upconversion;
 use crate::write::{self, WriteOptions};
 use crate::DataRequest;
@@ -36,6 +43,9 @@ pub(crate) static IMAGES_DIR: &str = "images";
 ///
 /// See the [UFO specification] for a description of the underlying data.
 ///
+/// Cloning a font is cheap relative to loading one: glyphs are shared between
+/// the clones and only copied when first modified. See [`Layer`] for details.
+///
 /// [UFO specification]: https://unifiedfontobject.org/versions/ufo3/
 /// [UFO directory]: https://unifiedfontobject.org/versions/ufo3/index.html#directory-structure
 #[derive(Clone, Debug, Default, PartialEq)]
@@ -77,6 +87,12 @@ pub struct Font {
     pub kerning: Kerning,
     /// The contents of the [`features.fea`][fea] file, if one exists.
     ///
+    /// If the font was loaded with [`DataRequest::lazy_features`], this will be
+    /// empty until [`Font::features_mut`] is called. Assigning a non-empty string
+    /// before then replaces the features on disk: it is what will be saved, and
+    /// the file is no longer read. To remove the features of such a font, clear
+    /// the string returned by [`Font::features_mut`].
+    ///
     /// [fea]: https://unifiedfontobject.org/versions/ufo3/features.fea/
     pub features: String,
     /// The contents of the font's [`data` directory][dir].
@@ -87,6 +103,8 @@ pub struct Font {
     ///
     /// [dir]: https://unifiedfontobject.org/versions/ufo3/images/
     pub images: ImageStore,
+    /// The path of a `features.fea` file that has not been read yet.
+    lazy_features: Option<PathBuf>,
 }
 
 /// A version of the [UFO spec].
@@ -132,6 +150,29 @@ impl Default for MetaInfo {
     }
 }
 
+/// The effects that saving a [`Font`] would have on disk, as returned by
+/// [`Font::plan_save`].
+///
+/// All paths include the target directory passed to [`Font::plan_save`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SavePlan {
+    /// Files that do not currently exist and would be written.
+    pub creates: BTreeSet<PathBuf>,
+    /// Files that currently exist and would be written again.
+    pub overwrites: BTreeSet<PathBuf>,
+    /// Files that currently exist and would be removed.
+    pub deletions: BTreeSet<PathBuf>,
+}
+
+impl SavePlan {
+    /// Returns an iterator over every file that would be written, whether it
+    /// is created or overwritten.
+    pub fn writes(&self) -> impl Iterator<Item = &PathBuf> {
+        self.creates.iter().chain(self.overwrites.iter())
+    }
+}
+
 impl Font {
     /// Returns a new, empty [`Font`] object.
     pub fn new() -> Self {
@@ -206,10 +247,86 @@ impl Font {
         path: impl AsRef<Path>,
         request: DataRequest,
     ) -> Result<Font, FontLoadError> {
-        Self::load_impl(path.as_ref(), request)
+        Self::load_impl(path.as_ref(), request, &mut Vec::new())
+    }
+
+    /// Like [`Font::load_requested_data`], but also returns the problems that
+    /// were tolerated while parsing glyphs.
+    ///
+    /// Warnings are only produced when the request is [lenient]; a strict load
+    /// fails on the same problems instead.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use norad::{DataRequest, Font};
+    ///
+    /// let request = DataRequest::default().lenient(true);
+    /// let (ufo, warnings) =
+    ///     Font::load_with_warnings("path/to/font.ufo", request).expect("failed to load");
+    /// for warning in &warnings {
+    ///     eprintln!("{warning}");
+    /// }
+    /// ```
+    ///
+    /// [lenient]: DataRequest::lenient
+    pub fn load_with_warnings(
+        path: impl AsRef<Path>,
+        request: DataRequest,
+    ) -> Result<(Font, Vec<LoadWarning>), FontLoadError> {
+        let mut warnings = Vec::new();
+        let font = Self::load_impl(path.as_ref(), request, &mut warnings)?;
+        Ok((font, warnings))
     }
 
-    fn load_impl(path: &Path, request: DataRequest) -> Result<Font, FontLoadError> {
+    /// Returns a [`Font`] read from a tar archive, such as one created with
+    /// `tar -czf MyFont.tar.gz MyFont.ufo`.
+    ///
+    /// The archive may be uncompressed or compressed with gzip, which is
+    /// detected from the data itself; no other compression is supported. The
+    /// UFO may either be at the root of the archive, or be the only directory
+    /// at the root that contains a `metainfo.plist` (such as `MyFont.ufo/`).
+    /// Symbolic and hard links in the archive are ignored.
+    ///
+    /// The archive is unpacked into a temporary directory, which is removed
+    /// before returning, so everything is loaded eagerly, as by [`Font::load`],
+    /// including the [data](Font::data) and [images](Font::images) stores.
+    ///
+    /// This requires the `tar` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use norad::Font;
+    ///
+    /// let archive = std::fs::File::open("path/to/font.ufo.tar.gz").expect("failed to open");
+    /// let ufo = Font::load_tar(archive).expect("failed to load");
+    /// ```
+    #[cfg(feature = "tar")]
+    pub fn load_tar<R: std::io::Read>(reader: R) -> Result<Font, FontLoadError> {
+        use std::io::BufRead;
+
+        let mut reader = std::io::BufReader::new(reader);
+        let is_gzip =
+            reader.fill_buf().map_err(FontLoadError::TarArchive)?.starts_with(&[0x1f, 0x8b]);
+        let dir = tempfile::tempdir().map_err(FontLoadError::TarArchive)?;
+        if is_gzip {
+            unpack_tar(flate2::read::MultiGzDecoder::new(reader), dir.path())?;
+        } else {
+            unpack_tar(reader, dir.path())?;
+        }
+
+        let font = Font::load(find_unpacked_ufo(dir.path())?)?;
+        font.data.load_all().map_err(FontLoadError::DataStore)?;
+        font.images.load_all().map_err(FontLoadError::ImagesStore)?;
+        Ok(font)
+    }
+
+    fn load_impl(
+        path: &Path,
+        request: DataRequest,
+        warnings: &mut Vec<LoadWarning>,
+    ) -> Result<Font, FontLoadError> {
         let metadata = path.metadata().map_err(FontLoadError::AccessUfoDir)?;
         if !metadata.is_dir() {
             return Err(FontLoadError::UfoNotADir);
@@ -248,14 +365,23 @@ impl Font {
         };
 
         let features_path = path.join(FEATURES_FILE);
+        // v1 fonts may have features stored in the lib, which we need to upconvert
+        let lazy = request.lazy_features && meta.format_version != FormatVersion::V1;
+        let mut lazy_features = None;
         let mut features = if request.features && features_path.exists() {
-            load_features(&features_path)?
+            if lazy {
+                lazy_features = Some(features_path);
+                String::new()
+            } else {
+                load_features(&features_path)?
+            }
         } else {
             Default::default()
         };
 
         let glyph_names = NameList::default();
-        let layers = load_layer_set(path, &meta, &glyph_names, &request.layers)?;
+        let layers =
+            load_layer_set(path, &meta, &glyph_names, &request.layers, request.lenient, warnings)?;
 
         let data = if request.data && path.join(DATA_DIR).exists() {
             DataStore::new(path).map_err(FontLoadError::DataStore)?
@@ -306,9 +432,42 @@ impl Font {
             features,
             data,
             images,
+            lazy_features,
         })
     }
 
+    /// Returns a mutable reference to the font's [`features`](Font::features),
+    /// reading them from disk first if they were requested lazily.
+    ///
+    /// When the font was loaded with [`DataRequest::lazy_features`], the
+    /// `features.fea` file is only read on the first call to this method; later
+    /// calls (and fonts loaded without lazy features) return the field directly.
+    /// If reading fails, the features stay unloaded and the next call will try
+    /// again.
+    ///
+    /// # Thread safety
+    ///
+    /// The deferred read happens through `&mut self`, so it can never race with
+    /// other access to the font. Fonts are not [`Sync`], so sharing one between
+    /// threads already requires a lock (or moving it), and the read happens
+    /// under whatever exclusive access that provides.
+    pub fn features_mut(&mut self) -> Result<&mut String, FontLoadError> {
+        if let Some(path) = &self.lazy_features {
+            // features assigned in the meantime take precedence over the file
+            if self.features.is_empty() {
+                self.features = load_features(path)?;
+            }
+            self.lazy_features = None;
+        }
+        Ok(&mut self.features)
+    }
+
+    /// Returns `true` if the font has a `features.fea` file that was requested
+    /// lazily and has not been read yet.
+    pub fn has_unloaded_features(&self) -> bool {
+        self.lazy_features.is_some() && self.features.is_empty()
+    }
+
     /// Serialize a [`Font`] to the given `path`, overwriting any existing contents.
     ///
     /// # Examples
@@ -417,7 +576,112 @@ impl Font {
         self.save_impl(path, options)
     }
 
-    fn save_impl(&self, path: &Path, options: &WriteOptions) -> Result<(), FontWriteError> {
+    /// Returns the files that [`Font::save`] would create, overwrite and delete
+    /// at `path`, without writing anything.
+    ///
+    /// This runs the same up-front validation as saving does, so an error here
+    /// means the save would fail before touching the file system. Since saving
+    /// replaces the target directory wholesale, every existing file that the
+    /// save would not write again shows up in [`SavePlan::deletions`].
+    ///
+    /// Note that glyphs are not validated, so an actual save may still fail
+    /// partway through.
+    pub fn plan_save(&self, path: impl AsRef<Path>) -> Result<SavePlan, FontWriteError> {
+        self.plan_save_with_options(path, &WriteOptions::default())
+    }
+
+    /// Returns the files that [`Font::save_with_options`] would create,
+    /// overwrite and delete at `path` with `options`, without writing anything.
+    ///
+    /// This takes into account the options that affect which files are
+    /// written, such as [`WriteOptions::reuse_file_names`]. See
+    /// [`Font::plan_save`] for details.
+    pub fn plan_save_with_options(
+        &self,
+        path: impl AsRef<Path>,
+        options: &WriteOptions,
+    ) -> Result<SavePlan, FontWriteError> {
+        let path = path.as_ref();
+        self.validate_for_save()?;
+        let existing_file_names = self.existing_file_names(path, options);
+
+        let mut existing = BTreeSet::new();
+        if path.exists() {
+            collect_files(path, &mut existing)?;
+        }
+
+        let mut plan = SavePlan::default();
+        for file in self.planned_files(&existing_file_names).into_iter().map(|file| path.join(file))
+        {
+            if existing.remove(&file) {
+                plan.overwrites.insert(file);
+            } else {
+                plan.creates.insert(file);
+            }
+        }
+        plan.deletions = existing;
+        Ok(plan)
+    }
+
+    /// Returns the paths, relative to the UFO directory, of every file that
+    /// [`Font::save_impl`] writes, given the layers' `existing_file_names`.
+    /// This must be kept in sync with it.
+    fn planned_files(
+        &self,
+        existing_file_names: &[Option<BTreeMap<Name, PathBuf>>],
+    ) -> Vec<PathBuf> {
+        let mut files = vec![PathBuf::from(METAINFO_FILE)];
+        if !self.font_info.is_empty() {
+            files.push(FONTINFO_FILE.into());
+        }
+        if !self.lib.is_empty() || !self.font_info.dump_object_libs().is_empty() {
+            files.push(LIB_FILE.into());
+        }
+        if !self.groups.is_empty() {
+            files.push(GROUPS_FILE.into());
+        }
+        if !self.kerning.is_empty() {
+            files.push(KERNING_FILE.into());
+        }
+        if !self.features.is_empty()
+            || self
+                .lazy_features
+                .as_ref()
+                .is_some_and(|path| fs::metadata(path).is_ok_and(|metadata| metadata.len() > 0))
+        {
+            files.push(FEATURES_FILE.into());
+        }
+        files.push(LAYER_CONTENTS_FILE.into());
+        for (layer, existing) in self.layers.iter().zip(existing_file_names) {
+            let layer_files = layer.planned_files(existing.as_ref());
+            files.extend(layer_files.into_iter().map(|file| layer.path.join(file)));
+        }
+        files.extend(self.data.iter().map(|(path, _)| Path::new(DATA_DIR).join(path)));
+        files.extend(self.images.iter().map(|(path, _)| Path::new(IMAGES_DIR).join(path)));
+        files
+    }
+
+    /// Returns, for each layer, the glyph file names to reuse from the UFO at
+    /// `path`, if `options` ask for it and the layer has a readable
+    /// `contents.plist` there.
+    fn existing_file_names(
+        &self,
+        path: &Path,
+        options: &WriteOptions,
+    ) -> Vec<Option<BTreeMap<Name, PathBuf>>> {
+        self.layers
+            .iter()
+            .map(|layer| {
+                options
+                    .reuse_file_names
+                    .then(|| Layer::read_file_names(&path.join(&layer.path)))
+                    .flatten()
+            })
+            .collect()
+    }
+
+    /// Checks everything that can be checked before touching the file system.
+    fn validate_for_save(&self) -> Result<(), FontWriteError> {
         if self.meta.format_version != FormatVersion::V3 {
             return Err(FontWriteError::Downgrade);
         }
@@ -440,6 +704,27 @@ impl Font {
 
         // TODO: run glif validation up front?
 
+        Ok(())
+    }
+
+    fn save_impl(&self, path: &Path, options: &WriteOptions) -> Result<(), FontWriteError> {
+        self.validate_for_save()?;
+
+        // Read lazily requested features before potentially deleting them from disk,
+        // unless they have been replaced in memory.
+        let lazy_features = match &self.lazy_features {
+            Some(features_path) if self.features.is_empty() => {
+                Some(fs::read_to_string(features_path).map_err(|source| {
+                    FontWriteError::ReadExisting { path: features_path.clone(), source }
+                })?)
+            }
+            _ => None,
+        };
+        let features = lazy_features.as_ref().unwrap_or(&self.features);
+
+        // Read the glyph file names to reuse before deleting them from disk.
+        let existing_file_names = self.existing_file_names(path, options);
+
         // Now do the actual writing.
         if path.exists() {
             fs::remove_dir_all(path).map_err(FontWriteError::Cleanup)?;
@@ -491,15 +776,15 @@ impl Font {
                 .map_err(|source| FontWriteError::CustomFile { name: KERNING_FILE, source })?;
         }
 
-        if !self.features.is_empty() {
+        if !features.is_empty() {
             // Normalize feature files with line feed line endings
             // This is consistent with the line endings serialized in glif and plist files
             let feature_file_path = path.join(FEATURES_FILE);
-            if self.features.as_bytes().contains(&b'\r') {
-                close_already::fs::write(&feature_file_path, self.features.replace("\r\n", "\n"))
+            if features.as_bytes().contains(&b'\r') {
+                close_already::fs::write(&feature_file_path, features.replace("\r\n", "\n"))
                     .map_err(FontWriteError::FeatureFile)?;
             } else {
-                close_already::fs::write(&feature_file_path, &self.features)
+                close_already::fs::write(&feature_file_path, features)
                     .map_err(FontWriteError::FeatureFile)?;
             }
         }
@@ -509,14 +794,19 @@ impl Font {
         write::write_xml_to_file(&path.join(LAYER_CONTENTS_FILE), &contents, options)
             .map_err(|source| FontWriteError::CustomFile { name: LAYER_CONTENTS_FILE, source })?;
 
-        for layer in self.layers.iter() {
+        for (layer, existing) in self.layers.iter().zip(&existing_file_names) {
             let layer_path = path.join(&layer.path);
-            layer.save_with_options(&layer_path, options).map_err(|source| {
-                FontWriteError::Layer {
-                    name: layer.name.to_string(),
-                    path: layer_path,
-                    source: Box::new(source),
+            match existing {
+                Some(existing) => {
+                    let contents = layer.contents_reusing(existing);
+                    layer.save_with_contents(&layer_path, options, &contents)
                 }
+                None => layer.save_with_options(&layer_path, options),
+            }
+            .map_err(|source| FontWriteError::Layer {
+                name: layer.name.to_string(),
+                path: layer_path,
+                source: Box::new(source),
             })?;
         }
 
@@ -552,6 +842,14 @@ impl Font {
         Ok(())
     }
 
+    /// Removes any non-default layers that contain no glyphs, returning their names.
+    ///
+    /// The default layer is never removed, even if it is empty. The removed
+    /// layers will no longer be listed in `layercontents.plist` on save.
+    pub fn remove_empty_layers(&mut self) -> Vec<Name> {
+        self.layers.remove_empty_layers()
+    }
+
     /// Returns a reference to the default layer.
     pub fn default_layer(&self) -> &Layer {
         self.layers.default_layer()
@@ -567,6 +865,42 @@ impl Font {
         self.layers.iter()
     }
 
+    /// Splits the font into one standalone font per layer, each paired with the
+    /// name of its layer, in layer order.
+    ///
+    /// This is useful when each layer is used as a separate interpolation
+    /// master, as designspace sources can do.
+    ///
+    /// Each font has a single layer: a copy of the original layer, promoted to
+    /// be the default layer. The layer keeps its name, color and lib. The other
+    /// layers are dropped. Everything else (the metainfo, font info, lib,
+    /// groups, kerning, features, data and images) is duplicated into every
+    /// font as is, so per-master kerning, for instance, has to be adjusted
+    /// afterwards.
+    ///
+    /// Glyphs are shared with this font and only copied when modified, so
+    /// splitting is cheap.
+    pub fn split_layers_into_fonts(&self) -> Vec<(String, Font)> {
+        self.layers
+            .iter()
+            .map(|layer| {
+                let font = Font {
+                    meta: self.meta.clone(),
+                    font_info: self.font_info.clone(),
+                    layers: LayerContents::from_layer(layer),
+                    lib: self.lib.clone(),
+                    groups: self.groups.clone(),
+                    kerning: self.kerning.clone(),
+                    features: self.features.clone(),
+                    data: self.data.clone(),
+                    images: self.images.clone(),
+                    lazy_features: self.lazy_features.clone(),
+                };
+                (layer.name().to_string(), font)
+            })
+            .collect()
+    }
+
     /// Returns an iterator over all the glyph names _in the default layer_.
     pub fn iter_names(&self) -> impl Iterator<Item = Name> + '_ {
         //FIXME: why not &Name here?
@@ -590,6 +924,321 @@ impl Font {
         self.default_layer().len()
     }
 
+    /// Returns the names of all glyphs _in the default layer_, in glyph order.
+    ///
+    /// Glyphs listed in the lib's `public.glyphOrder` key come first, in the
+    /// order listed; entries for glyphs that do not exist are skipped. Any
+    /// remaining glyphs follow in alphabetical order.
+    pub fn glyph_order(&self) -> Vec<Name> {
+        let layer = self.default_layer();
+        let mut seen = HashSet::new();
+        let mut order: Vec<Name> = self
+            .lib
+            .get(PUBLIC_GLYPH_ORDER_KEY)
+            .and_then(plist::Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(plist::Value::as_string)
+            .filter_map(|name| layer.glyphs.get_key_value(name).map(|(name, _)| name))
+            .filter(|name| seen.insert(*name))
+            .cloned()
+            .collect();
+        order.extend(layer.glyphs.keys().filter(|name| !seen.contains(name)).cloned());
+        order
+    }
+
+    /// Sets the glyph order, stored in the lib's `public.glyphOrder` key.
+    ///
+    /// Glyphs _in the default layer_ that are missing from `order` are appended
+    /// in their current [glyph order](Font::glyph_order), so the stored order
+    /// always lists every glyph.
+    ///
+    /// Returns an error, without modifying the font, if `order` contains a name
+    /// that is not in the default layer or contains the same name twice.
+    pub fn set_glyph_order(&mut self, order: Vec<String>) -> Result<(), NamingError> {
+        let layer = self.default_layer();
+        let mut seen = HashSet::new();
+        for name in &order {
+            if !layer.contains_glyph(name) {
+                return Err(NamingError::Missing(name.clone()));
+            }
+            if !seen.insert(name.as_str()) {
+                return Err(NamingError::Duplicate(name.clone()));
+            }
+        }
+        let remaining: Vec<_> =
+            self.glyph_order().into_iter().filter(|name| !seen.contains(name.as_str())).collect();
+        let order: Vec<plist::Value> = order
+            .into_iter()
+            .map(plist::Value::String)
+            .chain(remaining.iter().map(|name| plist::Value::String(name.to_string())))
+            .collect();
+        self.lib.insert(PUBLIC_GLYPH_ORDER_KEY.into(), order.into());
+        Ok(())
+    }
+
+    /// Returns an iterator over the glyphs _in the default layer_, in
+    /// [glyph order](Font::glyph_order).
+    pub fn iter_ordered_glyphs(&self) -> impl Iterator<Item = &Glyph> + '_ {
+        let layer = self.default_layer();
+        self.glyph_order().into_iter().filter_map(move |name| layer.get_glyph(&name))
+    }
+
+    /// Returns the names of the glyphs that should not be exported, stored in
+    /// the lib's `public.skipExportGlyphs` key.
+    ///
+    /// The names are returned as stored, and may include glyphs that do not
+    /// exist in the font.
+    pub fn skip_export_glyphs(&self) -> Vec<String> {
+        lib_string_list(&self.lib, PUBLIC_SKIP_EXPORT_GLYPHS_KEY)
+    }
+
+    /// Sets the glyphs that should not be exported, stored in the lib's
+    /// `public.skipExportGlyphs` key.
+    ///
+    /// Names of glyphs that do not exist in the font are allowed. An empty
+    /// list removes the key.
+    pub fn set_skip_export_glyphs(&mut self, names: Vec<String>) {
+        set_lib_string_list(&mut self.lib, PUBLIC_SKIP_EXPORT_GLYPHS_KEY, names);
+    }
+
+    /// Returns an iterator over the glyphs _in the default layer_ that should be
+    /// exported, in [glyph order](Font::glyph_order).
+    ///
+    /// This is [`Font::iter_ordered_glyphs`], without the glyphs listed in
+    /// [`Font::skip_export_glyphs`].
+    pub fn iter_export_glyphs(&self) -> impl Iterator<Item = &Glyph> + '_ {
+        let skipped: HashSet<String> = self.skip_export_glyphs().into_iter().collect();
+        self.iter_ordered_glyphs().filter(move |glyph| !skipped.contains(glyph.name().as_str()))
+    }
+
+    /// Returns an iterator over the glyphs _in the default layer_ that mix cubic
+    /// and quadratic curves, as reported by [`Glyph::curve_types`].
+    pub fn iter_mixed_curve_glyphs(&self) -> impl Iterator<Item = &Glyph> + '_ {
+        self.default_layer().iter().filter(|glyph| glyph.curve_types() == CurveTypeSummary::Mixed)
+    }
+
+    /// Computes a [`SpacingReport`] for the layer named `layer`, or returns
+    /// `None` if there is no such layer.
+    ///
+    /// Sidebearings are measured as by [`Glyph::sidebearings`], decomposing
+    /// components using the base glyphs from the same layer. Glyphs without
+    /// an outline have no sidebearings.
+    ///
+    /// The report is cached with the layer, and only recomputed once a glyph
+    /// in the layer has been added, removed or accessed mutably.
+    ///
+    /// [`Glyph::sidebearings`]: crate::Glyph::sidebearings
+    pub fn spacing_report(&self, layer: &str) -> Option<SpacingReport> {
+        let layer = self.layers.get(layer)?;
+        Some(
+            layer.spacing_cache.get_or_insert_with(&layer.glyphs, || compute_spacing_report(layer)),
+        )
+    }
+
+    /// Renames the group `old` to `new`, updating every kerning pair that refers to it.
+    ///
+    /// Returns an error if `new` is not a valid name, if there is no group named
+    /// `old`, or if a group named `new` already exists.
+    ///
+    /// While kerning refers to `old`, its kerning side has to be kept: renaming
+    /// a `public.kern1.` group to anything other than a `public.kern1.` name (and
+    /// likewise for `public.kern2.`, or a non-kerning group to a kerning group
+    /// name) returns [`NamingError::Invalid`].
+    pub fn rename_group(&mut self, old: &str, new: &str) -> Result<(), NamingError> {
+        let new = Name::new(new).map_err(|_| NamingError::Invalid(new.into()))?;
+        if !self.groups.contains_key(old) {
+            return Err(NamingError::Missing(old.into()));
+        }
+        if old == new.as_str() {
+            return Ok(());
+        }
+        if self.groups.contains_key(&new) {
+            return Err(NamingError::Duplicate(new.to_string()));
+        }
+        let kern_side = |name: &str| {
+            ["public.kern1.", "public.kern2."].iter().position(|prefix| name.starts_with(prefix))
+        };
+        let in_kerning = self.kerning.contains_key(old)
+            || self.kerning.values().any(|pairs| pairs.contains_key(old));
+        if in_kerning && kern_side(old) != kern_side(&new) {
+            return Err(NamingError::Invalid(new.to_string()));
+        }
+
+        let members = self.groups.remove(old).unwrap();
+        self.groups.insert(new.clone(), members);
+
+        if let Some(pairs) = self.kerning.remove(old) {
+            self.kerning.insert(new.clone(), pairs);
+        }
+        for pairs in self.kerning.values_mut() {
+            if let Some(value) = pairs.remove(old) {
+                pairs.insert(new.clone(), value);
+            }
+        }
+        Ok(())
+    }
+
+    /// Sets the kerning value of each `(first, second, value)` pair, adding the
+    /// pairs that don't exist yet, and returns the number of distinct pairs set.
+    ///
+    /// Returns an error, leaving the kerning unchanged, if any of the names is
+    /// not a valid name. If a pair occurs more than once, the last value wins,
+    /// and the pair is counted once.
+    pub fn set_kerning_pairs<'a>(
+        &mut self,
+        pairs: impl IntoIterator<Item = (&'a str, &'a str, f64)>,
+    ) -> Result<usize, NamingError> {
+        let name = |name: &str| Name::new(name).map_err(|_| NamingError::Invalid(name.into()));
+        let pairs = pairs
+            .into_iter()
+            .map(|(first, second, value)| Ok((name(first)?, name(second)?, value)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut set = HashSet::new();
+        for (first, second, value) in pairs {
+            set.insert((first.clone(), second.clone()));
+            self.kerning.entry(first).or_default().insert(second, value);
+        }
+        Ok(set.len())
+    }
+
+    /// Adds `delta` to the value of every kerning pair, and returns the number
+    /// of pairs changed.
+    ///
+    /// Pairs whose value becomes zero are kept, as they may still override
+    /// the value of a group pair.
+    pub fn adjust_kerning(&mut self, delta: f64) -> usize {
+        let mut count = 0;
+        for value in self.kerning.values_mut().flat_map(|pairs| pairs.values_mut()) {
+            *value += delta;
+            count += 1;
+        }
+        count
+    }
+
+    /// Removes each `(first, second)` kerning pair, and returns the number of
+    /// pairs that existed and were removed.
+    ///
+    /// Pairs that don't exist are ignored. A first side that is left without
+    /// pairs is removed entirely, so that it isn't written out as an empty
+    /// dictionary.
+    pub fn remove_kerning_pairs<'a>(
+        &mut self,
+        pairs: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> usize {
+        let mut count = 0;
+        for (first, second) in pairs {
+            let Some(seconds) = self.kerning.get_mut(first) else {
+                continue;
+            };
+            if seconds.remove(second).is_some() {
+                count += 1;
+                if seconds.is_empty() {
+                    self.kerning.remove(first);
+                }
+            }
+        }
+        count
+    }
+
+    /// Checks the font for inconsistencies, returning every problem found.
+    ///
+    /// Unlike the checks that run when saving, these problems do not prevent the
+    /// font from being written, but they usually indicate corrupted data that
+    /// will cause trouble in other tools. The following are checked:
+    ///
+    /// - every glyph listed in the lib's `public.glyphOrder`, in a group, or in a
+    ///   kerning pair exists in the default layer;
+    /// - every kerning group used in a kerning pair exists in the groups;
+    /// - no codepoint is assigned to more than one glyph in the default layer;
+    /// - every glyph in every layer passes [`Glyph::validate`], which checks, for
+    ///   instance, that the base glyphs of its components exist in its layer.
+    ///
+    /// An empty list means no problems were found.
+    pub fn validate(&self) -> Vec<FontValidationError> {
+        let mut errors = Vec::new();
+        self.validate_references(&mut errors);
+        self.validate_codepoints(&mut errors);
+        self.validate_glyphs(&mut errors);
+        errors
+    }
+
+    /// Reports the problems found by [`Glyph::validate`] in every layer.
+    fn validate_glyphs(&self, errors: &mut Vec<FontValidationError>) {
+        for layer in self.layers.iter() {
+            for glyph in layer.iter() {
+                errors.extend(glyph.validate(layer).into_iter().map(|source| {
+                    FontValidationError::Glyph {
+                        layer: layer.name().clone(),
+                        glyph: glyph.name().clone(),
+                        source,
+                    }
+                }));
+            }
+        }
+    }
+
+    /// Reports codepoints claimed by more than one glyph in the default layer.
+    fn validate_codepoints(&self, errors: &mut Vec<FontValidationError>) {
+        let mut glyphs_by_codepoint: BTreeMap<char, Vec<Name>> = BTreeMap::new();
+        for glyph in self.default_layer().iter() {
+            for codepoint in glyph.codepoints.iter() {
+                glyphs_by_codepoint.entry(codepoint).or_default().push(glyph.name().clone());
+            }
+        }
+        errors.extend(glyphs_by_codepoint.into_iter().filter(|(_, glyphs)| glyphs.len() > 1).map(
+            |(codepoint, glyphs)| FontValidationError::DuplicateCodepoint { codepoint, glyphs },
+        ));
+    }
+
+    /// Reports glyph and group names that are referenced but don't exist.
+    fn validate_references(&self, errors: &mut Vec<FontValidationError>) {
+        let layer = self.default_layer();
+        if let Some(order) = self.lib.get(PUBLIC_GLYPH_ORDER_KEY).and_then(plist::Value::as_array) {
+            for (index, name) in order.iter().enumerate() {
+                if let Some(name) = name.as_string().filter(|name| !layer.contains_glyph(name)) {
+                    errors.push(FontValidationError::MissingGlyph {
+                        name: Name::new_raw(name),
+                        location: ReferenceLocation::GlyphOrder { index },
+                    });
+                }
+            }
+        }
+
+        for (group, members) in &self.groups {
+            for name in members.iter().filter(|name| !layer.contains_glyph(name)) {
+                errors.push(FontValidationError::MissingGlyph {
+                    name: name.clone(),
+                    location: ReferenceLocation::Group { group: group.clone() },
+                });
+            }
+        }
+
+        for (first, pairs) in &self.kerning {
+            for second in pairs.keys() {
+                for name in [first, second] {
+                    let location = || ReferenceLocation::Kerning {
+                        first: first.clone(),
+                        second: second.clone(),
+                    };
+                    if name.starts_with("public.kern1.") || name.starts_with("public.kern2.") {
+                        if !self.groups.contains_key(name) {
+                            errors.push(FontValidationError::MissingGroup {
+                                name: name.clone(),
+                                location: location(),
+                            });
+                        }
+                    } else if !layer.contains_glyph(name) {
+                        errors.push(FontValidationError::MissingGlyph {
+                            name: name.clone(),
+                            location: location(),
+                        });
+                    }
+                }
+            }
+        }
+    }
+
     /// Return the font's global guidelines, stored in [`FontInfo`].
     pub fn guidelines(&self) -> &[Guideline] {
         self.font_info.guidelines.as_deref().unwrap_or(&[])
@@ -633,6 +1282,74 @@ fn load_kerning(kerning_path: &Path) -> Result<Kerning, FontLoadError> {
     Ok(kerning)
 }
 
+fn compute_spacing_report(layer: &Layer) -> SpacingReport {
+    let mut report = SpacingReport::default();
+    let mut widths = Vec::with_capacity(layer.len());
+    for glyph in layer.iter() {
+        report.glyph_count += 1;
+        widths.push(glyph.width);
+        if glyph.width == 0.0 {
+            report.zero_width_glyphs.push(glyph.name().clone());
+        }
+        match glyph.sidebearings(Some(layer)) {
+            Ok(Some((left, right))) if left < 0.0 || right < 0.0 => {
+                report.negative_sidebearings.push(NegativeSidebearing {
+                    glyph: glyph.name().clone(),
+                    left,
+                    right,
+                });
+            }
+            Ok(_) => (),
+            Err(_) => report.unmeasured_glyphs.push(glyph.name().clone()),
+        }
+    }
+
+    widths.sort_by(f64::total_cmp);
+    for width in widths {
+        match report.advance_widths.last_mut() {
+            Some(last) if last.width == width => last.count += 1,
+            _ => report.advance_widths.push(WidthCount { width, count: 1 }),
+        }
+    }
+    report
+}
+
+/// Unpacks the tar archive read from `reader` into `dir`, skipping links.
+#[cfg(feature = "tar")]
+fn unpack_tar(reader: impl std::io::Read, dir: &Path) -> Result<(), FontLoadError> {
+    let mut archive = tar::Archive::new(reader);
+    for entry in archive.entries().map_err(FontLoadError::TarArchive)? {
+        let mut entry = entry.map_err(FontLoadError::TarArchive)?;
+        let entry_type = entry.header().entry_type();
+        if entry_type.is_symlink() || entry_type.is_hard_link() {
+            continue;
+        }
+        // `unpack_in` refuses to write outside of `dir`
+        entry.unpack_in(dir).map_err(FontLoadError::TarArchive)?;
+    }
+    Ok(())
+}
+
+/// Returns the UFO in an unpacked archive: either `dir` itself, or its only
+/// subdirectory containing a metainfo.plist file.
+#[cfg(feature = "tar")]
+fn find_unpacked_ufo(dir: &Path) -> Result<PathBuf, FontLoadError> {
+    if dir.join(METAINFO_FILE).is_file() {
+        return Ok(dir.to_owned());
+    }
+    let mut candidates = Vec::new();
+    for entry in fs::read_dir(dir).map_err(FontLoadError::TarArchive)? {
+        let path = entry.map_err(FontLoadError::TarArchive)?.path();
+        if path.join(METAINFO_FILE).is_file() {
+            candidates.push(path);
+        }
+    }
+    match candidates.pop() {
+        Some(path) if candidates.is_empty() => Ok(path),
+        _ => Err(FontLoadError::MissingMetaInfoFile),
+    }
+}
+
 fn load_features(features_path: &Path) -> Result<String, FontLoadError> {
     let features = fs::read_to_string(features_path).map_err(FontLoadError::FeatureFile)?;
     Ok(features)
@@ -643,12 +1360,28 @@ fn load_layer_set(
     meta: &MetaInfo,
     glyph_names: &NameList,
     filter: &LayerFilter,
+    lenient: bool,
+    warnings: &mut Vec<LoadWarning>,
 ) -> Result<LayerContents, FontLoadError> {
     let layercontents_path = ufo_path.join(LAYER_CONTENTS_FILE);
     if meta.format_version == FormatVersion::V3 && !layercontents_path.exists() {
         return Err(FontLoadError::MissingLayerContentsFile);
     }
-    LayerContents::load(ufo_path, glyph_names, filter)
+    LayerContents::load(ufo_path, glyph_names, filter, lenient, warnings)
+}
+
+/// Recursively collects all files below `dir`.
+fn collect_files(dir: &Path, files: &mut BTreeSet<PathBuf>) -> Result<(), FontWriteError> {
+    let read_err = |source| FontWriteError::ReadExisting { path: dir.to_owned(), source };
+    for entry in fs::read_dir(dir).map_err(read_err)? {
+        let path = entry.map_err(read_err)?.path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.insert(path);
+        }
+    }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -657,7 +1390,8 @@ mod tests {
 
     use tempfile::TempDir;
 
-    use crate::error::LayerLoadError;
+    use crate::error::{GlyphValidationError, LayerLoadError};
+    use crate::{AffineTransform, Component, Contour, ContourPoint, PointType};
 
     use super::*;
 
@@ -713,6 +1447,39 @@ mod tests {
         assert_eq!(test_fea, expected_fea);
     }
 
+    #[test]
+    fn lazy_features() {
+        let path = "testdata/MutatorSansLightWide.ufo";
+        let request = DataRequest::default().lazy_features(true);
+        let mut font = Font::load_requested_data(path, request).unwrap();
+        assert!(font.has_unloaded_features());
+        assert!(font.features.is_empty());
+
+        // saving reads features that haven't been accessed yet
+        let tmp = TempDir::new().unwrap();
+        font.save(&tmp).unwrap();
+        let saved = fs::read_to_string(tmp.path().join(FEATURES_FILE)).unwrap();
+        assert_eq!(saved, "# this is the feature from lightWide\n");
+
+        assert_eq!(font.features_mut().unwrap(), "# this is the feature from lightWide\n");
+        assert!(!font.has_unloaded_features());
+        assert_eq!(font, Font::load(path).unwrap());
+    }
+
+    #[test]
+    fn lazy_features_assigned_before_access() {
+        let path = "testdata/MutatorSansLightWide.ufo";
+        let request = DataRequest::default().lazy_features(true);
+        let mut font = Font::load_requested_data(path, request).unwrap();
+        font.features = "# replaced\n".into();
+        assert!(!font.has_unloaded_features());
+
+        let tmp = TempDir::new().unwrap();
+        font.save(&tmp).unwrap();
+        assert_eq!(Font::load(&tmp).unwrap().features, "# replaced\n");
+        assert_eq!(font.features_mut().unwrap(), "# replaced\n");
+    }
+
     #[test]
     fn loading_invalid_ufo_dir_path() {
         let path = "totally/bogus/filepath/font.ufo";
@@ -778,6 +1545,89 @@ mod tests {
         assert!(font_obj.features.is_empty());
     }
 
+    #[test]
+    fn load_with_warnings() {
+        let dir = TempDir::new().unwrap();
+        let ufo_path = dir.path().join("test.ufo");
+        let mut font = Font::new();
+        font.default_layer_mut().insert_glyph(Glyph::new("a"));
+        font.save(&ufo_path).unwrap();
+        fs::write(ufo_path.join("glyphs/a.glif"), include_bytes!("../testdata/nan_advance.glif"))
+            .unwrap();
+
+        assert!(Font::load(&ufo_path).is_err());
+        let (font, warnings) =
+            Font::load_with_warnings(&ufo_path, DataRequest::default().lenient(true)).unwrap();
+        assert_eq!(font.default_layer().get_glyph("a").unwrap().width, 0.0);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].layer.as_str(), "public.default");
+        assert_eq!(warnings[0].glyph.as_str(), "a");
+        assert!(matches!(
+            warnings[0].warning,
+            crate::error::GlifLoadWarning::NonFiniteAdvance { attribute: "width", .. }
+        ));
+    }
+
+    #[cfg(feature = "tar")]
+    fn tar_archive(ufo: &Path, root: &str, gzip: bool) -> Vec<u8> {
+        let mut builder = tar::Builder::new(Vec::new());
+        builder.append_dir_all(root, ufo).unwrap();
+        let archive = builder.into_inner().unwrap();
+        if !gzip {
+            return archive;
+        }
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), Default::default());
+        std::io::Write::write_all(&mut encoder, &archive).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    #[cfg(feature = "tar")]
+    fn load_tar_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let ufo = dir.path().join("Test.ufo");
+        Font::load("testdata/dataimagetest.ufo").unwrap().save(&ufo).unwrap();
+        let font = Font::load(&ufo).unwrap();
+
+        for (root, gzip) in [("Test.ufo", false), ("Test.ufo", true), (".", false), (".", true)] {
+            let archive = tar_archive(&ufo, root, gzip);
+            let loaded = Font::load_tar(archive.as_slice()).unwrap();
+            assert_eq!(loaded, font, "root {root:?}, gzip {gzip}");
+            let path = Path::new("com.testing.random/zzz/z.txt");
+            assert_eq!(
+                loaded.data.get(path).unwrap().unwrap(),
+                font.data.get(path).unwrap().unwrap()
+            );
+            let path = Path::new("image1.png");
+            assert_eq!(
+                loaded.images.get(path).unwrap().unwrap(),
+                font.images.get(path).unwrap().unwrap()
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "tar")]
+    fn load_tar_without_single_ufo() {
+        let dir = TempDir::new().unwrap();
+        let mut builder = tar::Builder::new(Vec::new());
+        builder.append_dir_all("A.ufo", "testdata/dataimagetest.ufo").unwrap();
+        builder.append_dir_all("B.ufo", "testdata/dataimagetest.ufo").unwrap();
+        let archive = builder.into_inner().unwrap();
+        assert!(matches!(
+            Font::load_tar(archive.as_slice()),
+            Err(FontLoadError::MissingMetaInfoFile)
+        ));
+
+        std::fs::write(dir.path().join("README"), "not a font").unwrap();
+        let archive = tar_archive(dir.path(), ".", true);
+        assert!(matches!(
+            Font::load_tar(archive.as_slice()),
+            Err(FontLoadError::MissingMetaInfoFile)
+        ));
+        assert!(matches!(Font::load_tar(&b"nonsense"[..]), Err(FontLoadError::TarArchive(_))));
+    }
+
     #[test]
     fn upconvert_ufov1_robofab_data() {
         let path = "testdata/fontinfotest_v1.ufo";
@@ -867,4 +1717,443 @@ mod tests {
         let tmp = TempDir::new().unwrap();
         ufo.save_with_options(tmp, &opt).unwrap()
     }
+
+    #[test]
+    fn remove_empty_layers() {
+        let mut font = Font::load("testdata/MutatorSansLightWide.ufo").unwrap();
+        font.layers.new_layer("empty").unwrap();
+        font.default_layer_mut().clear();
+        assert_eq!(font.remove_empty_layers(), vec![Name::new_raw("empty")]);
+        assert_eq!(
+            font.layers.names().map(Name::as_str).collect::<Vec<_>>(),
+            ["foreground", "background"]
+        );
+
+        let tmp = TempDir::new().unwrap();
+        font.save(&tmp).unwrap();
+        let font = Font::load(&tmp).unwrap();
+        assert_eq!(
+            font.layers.names().map(Name::as_str).collect::<Vec<_>>(),
+            ["foreground", "background"]
+        );
+        assert!(font.default_layer().is_empty());
+        assert!(!font.layers.get("background").unwrap().is_empty());
+    }
+
+    #[test]
+    fn set_glyph_order() {
+        let mut font = Font::new();
+        for name in ["a", "b", "c", "d"] {
+            font.default_layer_mut().insert_glyph(Glyph::new(name));
+        }
+        assert_eq!(font.glyph_order(), ["a", "b", "c", "d"].map(Name::new_raw));
+
+        font.set_glyph_order(vec!["c".into(), "a".into()]).unwrap();
+        assert_eq!(font.glyph_order(), ["c", "a", "b", "d"].map(Name::new_raw));
+        let names: Vec<_> = font.iter_ordered_glyphs().map(|g| g.name().as_str()).collect();
+        assert_eq!(names, ["c", "a", "b", "d"]);
+
+        // omitted names keep their current relative order
+        font.set_glyph_order(vec!["d".into()]).unwrap();
+        assert_eq!(font.glyph_order(), ["d", "c", "a", "b"].map(Name::new_raw));
+
+        assert!(matches!(
+            font.set_glyph_order(vec!["a".into(), "z".into()]),
+            Err(NamingError::Missing(name)) if name == "z"
+        ));
+        assert!(matches!(
+            font.set_glyph_order(vec!["a".into(), "a".into()]),
+            Err(NamingError::Duplicate(name)) if name == "a"
+        ));
+        assert_eq!(font.glyph_order(), ["d", "c", "a", "b"].map(Name::new_raw));
+
+        let tmp = TempDir::new().unwrap();
+        font.save(&tmp).unwrap();
+        let font = Font::load(&tmp).unwrap();
+        assert_eq!(font.glyph_order(), ["d", "c", "a", "b"].map(Name::new_raw));
+    }
+
+    #[test]
+    fn skip_export_glyphs() {
+        let mut font = Font::new();
+        for name in ["a", "b", "c", "d"] {
+            font.default_layer_mut().insert_glyph(Glyph::new(name));
+        }
+        assert!(font.skip_export_glyphs().is_empty());
+        font.set_glyph_order(vec!["d".into(), "c".into()]).unwrap();
+        font.set_skip_export_glyphs(vec!["c".into(), "missing".into(), "a".into()]);
+
+        let tmp = TempDir::new().unwrap();
+        font.save(&tmp).unwrap();
+        let mut font = Font::load(&tmp).unwrap();
+        assert_eq!(font.skip_export_glyphs(), ["c", "missing", "a"]);
+        let names: Vec<_> = font.iter_export_glyphs().map(|g| g.name().as_str()).collect();
+        assert_eq!(names, ["d", "b"]);
+
+        font.set_skip_export_glyphs(Vec::new());
+        assert!(!font.lib.contains_key(PUBLIC_SKIP_EXPORT_GLYPHS_KEY));
+        assert_eq!(font.iter_export_glyphs().count(), 4);
+    }
+
+    #[test]
+    fn split_layers_into_fonts() {
+        let mut font = Font::load("testdata/MutatorSansLightWide.ufo").unwrap();
+        let bold = font.layers.new_layer("bold").unwrap();
+        bold.insert_glyph(Glyph::new("A"));
+        bold.insert_glyph(Glyph::new("B"));
+        bold.lib.insert("com.example.master".into(), "bold".into());
+
+        let split = font.split_layers_into_fonts();
+        let names: Vec<_> = split.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["foreground", "background", "bold"]);
+
+        let tmp = TempDir::new().unwrap();
+        for (name, split_font) in &split {
+            let path = tmp.path().join(format!("{name}.ufo"));
+            split_font.save(&path).unwrap();
+            let loaded = Font::load(&path).unwrap();
+            assert_eq!(loaded.layers.len(), 1);
+            assert_eq!(loaded.default_layer().name().as_str(), name);
+            let original = font.layers.get(name).unwrap();
+            assert!(loaded.iter_names().eq(original.iter().map(|g| g.name().clone())));
+            assert_eq!(loaded.font_info, font.font_info);
+            assert_eq!(loaded.kerning, font.kerning);
+            assert_eq!(loaded.groups, font.groups);
+        }
+        let bold = &split[2].1;
+        assert_eq!(bold.glyph_count(), 2);
+        assert_eq!(bold.default_layer().lib.get("com.example.master"), Some(&"bold".into()));
+    }
+
+    #[test]
+    fn set_kerning_pairs() {
+        let mut font = Font::new();
+        assert_eq!(font.set_kerning_pairs([("A", "V", -50.), ("A", "W", -40.)]).unwrap(), 2);
+        let pairs = [("A", "V", -60.), ("public.kern1.O", "A", 10.)];
+        assert_eq!(font.set_kerning_pairs(pairs).unwrap(), 2);
+        assert_eq!(font.kerning.len(), 2);
+        assert_eq!(font.kerning["A"]["V"], -60.);
+        assert_eq!(font.kerning["A"]["W"], -40.);
+        assert_eq!(font.kerning["public.kern1.O"]["A"], 10.);
+
+        // a repeated pair is counted once, and takes its last value
+        let pairs = [("T", "o", -80.), ("T", "a", -70.), ("T", "o", -90.)];
+        assert_eq!(font.set_kerning_pairs(pairs).unwrap(), 2);
+        assert_eq!(font.kerning["T"]["o"], -90.);
+
+        let before = font.kerning.clone();
+        let result = font.set_kerning_pairs([("T", "o", -80.), ("T", "", -80.)]);
+        assert!(matches!(result, Err(NamingError::Invalid(name)) if name.is_empty()));
+        assert_eq!(font.kerning, before);
+    }
+
+    #[test]
+    fn adjust_kerning() {
+        let mut font = Font::load("testdata/MutatorSansLightWide.ufo").unwrap();
+        let before = font.kerning.clone();
+        let pair_count: usize = before.values().map(BTreeMap::len).sum();
+        assert_eq!(font.adjust_kerning(10.), pair_count);
+        for (first, pairs) in &before {
+            for (second, value) in pairs {
+                assert_eq!(font.kerning[first][second], value + 10.);
+            }
+        }
+        assert_eq!(Font::new().adjust_kerning(10.), 0);
+    }
+
+    #[test]
+    fn remove_kerning_pairs() {
+        let mut font = Font::new();
+        font.set_kerning_pairs([("A", "V", -50.), ("A", "W", -40.), ("T", "o", -80.)]).unwrap();
+        assert_eq!(font.remove_kerning_pairs([("A", "V"), ("T", "o"), ("T", "o"), ("X", "Y")]), 2);
+        assert_eq!(font.kerning.len(), 1);
+        assert!(!font.kerning.contains_key("T"));
+        assert_eq!(font.kerning["A"].len(), 1);
+
+        // removing the last pairs doesn't leave an empty kerning.plist behind
+        assert_eq!(font.remove_kerning_pairs([("A", "W")]), 1);
+        assert!(font.kerning.is_empty());
+        let tmp = TempDir::new().unwrap();
+        font.save(tmp.path().join("test.ufo")).unwrap();
+        assert!(!tmp.path().join("test.ufo").join(KERNING_FILE).exists());
+    }
+
+    #[test]
+    fn iter_mixed_curve_glyphs() {
+        let mut font = Font::load("testdata/MutatorSansLightWide.ufo").unwrap();
+        assert_eq!(font.iter_mixed_curve_glyphs().count(), 0);
+        font.default_layer_mut().insert_glyph(Glyph::load("testdata/mixed_curves.glif").unwrap());
+        let mixed: Vec<_> = font.iter_mixed_curve_glyphs().map(|g| g.name().as_str()).collect();
+        assert_eq!(mixed, ["colon"]);
+    }
+
+    #[test]
+    fn rename_group() {
+        let mut font = Font::load("testdata/MutatorSansLightWide.ufo").unwrap();
+        font.groups.insert(Name::new_raw("public.kern2.O"), vec![Name::new_raw("O")]);
+        font.kerning
+            .entry(Name::new_raw("A"))
+            .or_default()
+            .insert(Name::new_raw("public.kern2.O"), -10.);
+        font.kerning
+            .entry(Name::new_raw("public.kern1.@MMK_L_A"))
+            .or_default()
+            .insert(Name::new_raw("public.kern2.O"), -20.);
+        let old = "public.kern2.O";
+        let new = "public.kern2.round";
+
+        font.rename_group(old, new).unwrap();
+        assert!(!font.groups.contains_key(old));
+        assert_eq!(font.groups.get(new), Some(&vec![Name::new_raw("O")]));
+        assert!(font.kerning.values().all(|pairs| !pairs.contains_key(old)));
+        assert_eq!(font.kerning["A"].get(new), Some(&-10.));
+        assert_eq!(font.kerning["public.kern1.@MMK_L_A"].get(new), Some(&-20.));
+
+        let old = "public.kern1.@MMK_L_A";
+        let new = "public.kern1.A";
+        let pairs = font.kerning[old].clone();
+        font.rename_group(old, new).unwrap();
+        assert!(!font.kerning.contains_key(old));
+        assert_eq!(font.kerning[new], pairs);
+
+        assert!(matches!(
+            font.rename_group("public.kern1.A", "public.kern2.round"),
+            Err(NamingError::Duplicate(_))
+        ));
+        assert!(matches!(
+            font.rename_group("nope", "public.kern1.B"),
+            Err(NamingError::Missing(_))
+        ));
+        assert!(font.groups.contains_key("public.kern1.A"));
+
+        // the kerning side can't change while kerning refers to the group
+        for new in ["public.kern2.A", "A"] {
+            assert!(
+                matches!(font.rename_group("public.kern1.A", new), Err(NamingError::Invalid(_))),
+                "{new}"
+            );
+        }
+        assert!(matches!(
+            font.rename_group("public.kern2.round", "public.kern1.round"),
+            Err(NamingError::Invalid(_))
+        ));
+        assert!(font.groups.contains_key("public.kern1.A"));
+        assert!(font.kerning.contains_key("public.kern1.A"));
+
+        // but it can when nothing refers to the group
+        font.groups.insert(Name::new_raw("public.kern1.unused"), vec![Name::new_raw("B")]);
+        font.rename_group("public.kern1.unused", "public.kern2.unused").unwrap();
+        font.rename_group("public.kern2.unused", "unused").unwrap();
+        assert!(font.groups.contains_key("unused"));
+    }
+
+    #[test]
+    fn validate_dangling_references() {
+        let font = Font::load("testdata/ufo/Tester-DanglingReferences.ufo").unwrap();
+        let missing_glyph = |name, location| FontValidationError::MissingGlyph {
+            name: Name::new_raw(name),
+            location,
+        };
+        let kerning = |first, second| ReferenceLocation::Kerning {
+            first: Name::new_raw(first),
+            second: Name::new_raw(second),
+        };
+        assert_eq!(
+            font.validate(),
+            vec![
+                missing_glyph("gone", ReferenceLocation::GlyphOrder { index: 1 }),
+                missing_glyph(
+                    "ghost",
+                    ReferenceLocation::Group { group: Name::new_raw("public.kern1.A") }
+                ),
+                missing_glyph("missing", kerning("a", "missing")),
+                FontValidationError::MissingGroup {
+                    name: Name::new_raw("public.kern2.nowhere"),
+                    location: kerning("b", "public.kern2.nowhere"),
+                },
+            ]
+        );
+        assert_eq!(
+            font.validate()[2].to_string(),
+            "glyph 'missing' referenced in kerning pair ('a', 'missing') does not exist in the default layer"
+        );
+
+        let font = Font::load("testdata/MutatorSansLightWide.ufo").unwrap();
+        assert_eq!(font.validate(), vec![]);
+    }
+
+    #[test]
+    fn validate_missing_component_base() {
+        let mut font = Font::new();
+        let glyph = Glyph::load("testdata/missing_component_base.glif").unwrap();
+        font.default_layer_mut().insert_glyph(Glyph::new("A"));
+        font.default_layer_mut().insert_glyph(glyph.clone());
+        // bases are looked up in the component's own layer
+        font.layers.new_layer("background").unwrap().insert_glyph(glyph);
+
+        let missing = |layer: &str, index, base: &str| FontValidationError::Glyph {
+            layer: Name::new_raw(layer),
+            glyph: Name::new_raw("Aacute"),
+            source: GlyphValidationError::MissingComponentBase { index, base: Name::new_raw(base) },
+        };
+        assert_eq!(
+            font.validate(),
+            [
+                missing("public.default", 1, "acutecomb"),
+                missing("background", 0, "A"),
+                missing("background", 1, "acutecomb"),
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_duplicate_codepoints() {
+        let font = Font::load("testdata/ufo/Tester-DuplicateCodepoints.ufo").unwrap();
+        let errors = font.validate();
+        assert_eq!(
+            errors,
+            vec![FontValidationError::DuplicateCodepoint {
+                codepoint: 'A',
+                glyphs: vec![Name::new_raw("A"), Name::new_raw("A.alt")],
+            }]
+        );
+        assert_eq!(
+            errors[0].to_string(),
+            "codepoint U+0041 is assigned to multiple glyphs: 'A', 'A.alt'"
+        );
+    }
+
+    #[test]
+    fn plan_save_matches_save() {
+        fn files_at(path: &Path) -> BTreeSet<PathBuf> {
+            let mut files = BTreeSet::new();
+            collect_files(path, &mut files).unwrap();
+            files
+        }
+
+        let tmp = TempDir::new().unwrap();
+        let ufo_path = tmp.path().join("test.ufo");
+        let mut font = Font::load("testdata/MutatorSansLightWide.ufo").unwrap();
+
+        let plan = font.plan_save(&ufo_path).unwrap();
+        assert!(plan.overwrites.is_empty());
+        assert!(plan.deletions.is_empty());
+        assert!(!ufo_path.exists());
+        font.save(&ufo_path).unwrap();
+        assert_eq!(files_at(&ufo_path), plan.creates);
+
+        let before = files_at(&ufo_path);
+        font.default_layer_mut().remove_glyph("A");
+        font.default_layer_mut().insert_glyph(Glyph::new("new"));
+        font.features.clear();
+        let plan = font.plan_save(&ufo_path).unwrap();
+        assert_eq!(files_at(&ufo_path), before);
+        assert_eq!(plan.creates, [ufo_path.join("glyphs/new.glif")].into());
+        assert_eq!(
+            plan.deletions,
+            [ufo_path.join("features.fea"), ufo_path.join("glyphs/A_.glif")].into()
+        );
+
+        font.save(&ufo_path).unwrap();
+        let after = files_at(&ufo_path);
+        assert_eq!(after, plan.writes().cloned().collect());
+        assert_eq!(before.difference(&after).cloned().collect::<BTreeSet<_>>(), plan.deletions);
+    }
+
+    fn spacing_test_font() -> Font {
+        let mut font = Font::new();
+        let layer = font.default_layer_mut();
+
+        let mut a = Glyph::new("a");
+        a.width = 120.;
+        a.contours.push(Contour::new(
+            [(0., 0.), (100., 0.), (100., 100.)]
+                .into_iter()
+                .map(|(x, y)| ContourPoint::new(x, y, PointType::Line, false, None, None, None))
+                .collect(),
+            None,
+            None,
+        ));
+        layer.insert_glyph(a);
+
+        let mut b = Glyph::new("b");
+        b.width = 200.;
+        let transform =
+            AffineTransform { x_scale: 2., y_scale: 2., x_offset: 10., ..Default::default() };
+        b.components.push(Component::new("a".into(), transform, None, None));
+        layer.insert_glyph(b);
+
+        let mut broken = Glyph::new("broken");
+        broken.width = 200.;
+        broken.components.push(Component::new("missing".into(), transform, None, None));
+        layer.insert_glyph(broken);
+
+        layer.insert_glyph(Glyph::new("zerowidth"));
+        font
+    }
+
+    #[test]
+    fn spacing_report() {
+        let font = spacing_test_font();
+        assert!(font.spacing_report("nope").is_none());
+
+        let report = font.spacing_report("public.default").unwrap();
+        assert_eq!(report.glyph_count, 4);
+        assert_eq!(
+            report.advance_widths,
+            [
+                WidthCount { width: 0., count: 1 },
+                WidthCount { width: 120., count: 1 },
+                WidthCount { width: 200., count: 2 },
+            ]
+        );
+        assert_eq!(report.zero_width_glyphs, [Name::new_raw("zerowidth")]);
+        assert_eq!(
+            report.negative_sidebearings,
+            [NegativeSidebearing { glyph: Name::new_raw("b"), left: 10., right: -10. }]
+        );
+        assert_eq!(report.unmeasured_glyphs, [Name::new_raw("broken")]);
+    }
+
+    #[test]
+    fn spacing_report_serde() {
+        let report = spacing_test_font().spacing_report("public.default").unwrap();
+        let value = plist::to_value(&report).unwrap();
+        let dict = value.as_dictionary().unwrap();
+        assert_eq!(dict.get("glyph_count").and_then(|v| v.as_unsigned_integer()), Some(4));
+        let parsed: SpacingReport = plist::from_value(&value).unwrap();
+        assert_eq!(parsed, report);
+    }
+
+    #[test]
+    fn spacing_report_cache() {
+        let mut font = spacing_test_font();
+        let layer = "public.default";
+        assert!(font.default_layer().spacing_cache.is_empty());
+        let report = font.spacing_report(layer).unwrap();
+        assert!(!font.default_layer().spacing_cache.is_empty());
+        assert_eq!(font.spacing_report(layer).unwrap(), report);
+
+        // mutating a glyph invalidates the report
+        font.default_layer_mut().get_glyph_mut("zerowidth").unwrap().width = 120.;
+        let report = font.spacing_report(layer).unwrap();
+        assert!(report.zero_width_glyphs.is_empty());
+        assert_eq!(report.advance_widths[0], WidthCount { width: 120., count: 2 });
+
+        // so do adding and removing glyphs
+        font.default_layer_mut().insert_glyph(Glyph::new("space"));
+        assert_eq!(font.spacing_report(layer).unwrap().glyph_count, 5);
+        font.default_layer_mut().remove_glyph("space");
+        font.default_layer_mut().remove_glyph("broken");
+        let report = font.spacing_report(layer).unwrap();
+        assert_eq!(report.glyph_count, 3);
+        assert!(report.unmeasured_glyphs.is_empty());
+
+        // a clone shares glyphs with the original, but is invalidated separately
+        let clone = font.clone();
+        assert_eq!(clone.spacing_report(layer).unwrap(), report);
+        font.default_layer_mut().get_glyph_mut("a").unwrap().width = 0.;
+        assert_eq!(font.spacing_report(layer).unwrap().zero_width_glyphs, [Name::new_raw("a")]);
+        assert_eq!(clone.spacing_report(layer).unwrap(), report);
+    }
 }
@@ -0,0 +1,142 @@
+//! Replacing components with the outlines of their base glyphs.
+
+use std::collections::HashSet;
+
+use super::{AffineTransform, Anchor, Contour, ContourPoint, Glyph};
+use crate::error::DecomposeError;
+use crate::{Layer, Name};
+
+/// Options for [`Glyph::decompose`].
+///
+/// # Examples
+///
+/// ```
+/// use norad::DecomposeOptions;
+///
+/// let options = DecomposeOptions::default().anchors(true);
+/// ```
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct DecomposeOptions {
+    /// Carry the anchors of base glyphs up into the decomposed glyph.
+    pub anchors: bool,
+}
+
+impl DecomposeOptions {
+    /// Request that the anchors of each component's base glyph be transformed
+    /// by the component transform and added to the decomposed glyph.
+    ///
+    /// Anchors are only carried up if no anchor with the same name exists
+    /// yet: the glyph's own anchors always take precedence, followed by those
+    /// of earlier components. Unnamed anchors are always carried up.
+    pub fn anchors(mut self, b: bool) -> Self {
+        self.anchors = b;
+        self
+    }
+}
+
+impl Glyph {
+    /// Replaces all components with the contours of their base glyphs, looked up
+    /// in `layer`, transformed by the component transform.
+    ///
+    /// Nested components are decomposed recursively. Identifiers and libs of
+    /// the copied contours, points and anchors are dropped, as they would no
+    /// longer be unique within the glyph.
+    ///
+    /// Returns an error, leaving the glyph unchanged, if a base glyph is missing
+    /// from the layer or if components reference each other in a cycle.
+    pub fn decompose(
+        &mut self,
+        layer: &Layer,
+        options: &DecomposeOptions,
+    ) -> Result<(), DecomposeError> {
+        let mut visiting = HashSet::from([self.name.clone()]);
+        let mut contours = Vec::new();
+        let mut anchors = Vec::new();
+        for component in &self.components {
+            decompose_into(
+                &component.base,
+                component.transform,
+                layer,
+                &mut visiting,
+                &mut contours,
+                options.anchors.then_some(&mut anchors),
+            )?;
+        }
+
+        self.components.clear();
+        self.contours.extend(contours);
+        let mut seen_names: HashSet<_> =
+            self.anchors.iter().filter_map(|a| a.name.clone()).collect();
+        for anchor in anchors {
+            if anchor.name.as_ref().map_or(true, |name| seen_names.insert(name.clone())) {
+                self.anchors.push(anchor);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Append the fully decomposed outline (and optionally the anchors) of the glyph
+/// `base` to `contours` and `anchors`, transformed by `transform`.
+fn decompose_into(
+    base: &Name,
+    transform: AffineTransform,
+    layer: &Layer,
+    visiting: &mut HashSet<Name>,
+    contours: &mut Vec<Contour>,
+    mut anchors: Option<&mut Vec<Anchor>>,
+) -> Result<(), DecomposeError> {
+    let glyph = layer.get_glyph(base).ok_or_else(|| DecomposeError::MissingBase(base.clone()))?;
+    if !visiting.insert(base.clone()) {
+        return Err(DecomposeError::Cycle(base.clone()));
+    }
+
+    contours.extend(glyph.contours.iter().map(|contour| {
+        let points = contour
+            .points
+            .iter()
+            .map(|point| {
+                let mut point = ContourPoint::new(
+                    point.x,
+                    point.y,
+                    point.typ.clone(),
+                    point.smooth,
+                    point.name.clone(),
+                    None,
+                    None,
+                );
+                point.transform(transform);
+                point
+            })
+            .collect();
+        Contour::new(points, None, None)
+    }));
+    if let Some(anchors) = anchors.as_deref_mut() {
+        anchors.extend(glyph.anchors.iter().map(|anchor| {
+            let mut anchor = Anchor::new(
+                anchor.x,
+                anchor.y,
+                anchor.name.clone(),
+                anchor.color.clone(),
+                None,
+                None,
+            );
+            (anchor.x, anchor.y) = transform.apply((anchor.x, anchor.y));
+            anchor
+        }));
+    }
+    for component in &glyph.components {
+        decompose_into(
+            &component.base,
+            transform.concat(component.transform),
+            layer,
+            visiting,
+            contours,
+            anchors.as_deref_mut(),
+        )?;
+    }
+
+    visiting.remove(base);
+    Ok(())
+}
@@ -0,0 +1,634 @@
+//! Geometric queries on contours and glyphs.
+
+use super::pen::draw_contour;
+use super::{AffineTransform, Contour, Glyph, Pen, PointType};
+use crate::error::DrawError;
+use crate::{Layer, Name};
+
+/// The number of straight lines each curve segment is flattened into.
+const CURVE_STEPS: usize = 16;
+
+/// Intersections closer together than this are reported once.
+const MERGE_DISTANCE: f64 = 1e-6;
+
+/// Curves are subdivided until their estimated length is within this distance
+/// of the true length.
+const LENGTH_TOLERANCE: f64 = 1e-3;
+
+/// The maximum number of times a curve is halved when measuring its length.
+const MAX_LENGTH_DEPTH: u32 = 24;
+
+/// Outlines with an absolute area below this have no centroid.
+const MIN_AREA: f64 = 1e-9;
+
+/// The nodes and weights of five-point Gauss-Legendre quadrature, mapped to
+/// the interval 0..1. This integrates polynomials of degree up to nine exactly,
+/// which covers the moments of cubic curves.
+const GAUSS_LEGENDRE: [(f64, f64); 5] = [
+    (0.046_910_077_030_668, 0.118_463_442_528_094_5),
+    (0.230_765_344_947_158_5, 0.239_314_335_249_683_2),
+    (0.5, 0.284_444_444_444_444_4),
+    (0.769_234_655_052_841_5, 0.239_314_335_249_683_2),
+    (0.953_089_922_969_332, 0.118_463_442_528_094_5),
+];
+
+impl Contour {
+    /// Whether the contour crosses or touches itself.
+    ///
+    /// See [`Contour::self_intersections`] for details.
+    pub fn self_intersects(&self) -> bool {
+        !self.self_intersections().is_empty()
+    }
+
+    /// Returns the approximate points where the contour crosses or touches itself.
+    ///
+    /// Curves are approximated by flattening them into straight lines, so the
+    /// reported points are only approximate (which is good enough to highlight
+    /// them in an editor), and curves that come very close to each other without
+    /// touching may be reported as well.
+    ///
+    /// A contour with an invalid point sequence has no intersections.
+    pub fn self_intersections(&self) -> Vec<(f64, f64)> {
+        let mut flattener = Flattener::default();
+        if draw_contour(self, AffineTransform::identity(), &mut flattener).is_none() {
+            return Vec::new();
+        }
+        polyline_intersections(&flattener.points, flattener.closed)
+    }
+}
+
+impl Glyph {
+    /// Returns the approximate points where any of the glyph's contours crosses
+    /// or touches itself, along with the index of the contour.
+    ///
+    /// Intersections between different contours, and in components, are not
+    /// reported. See [`Contour::self_intersections`] for details.
+    pub fn find_self_intersections(&self) -> Vec<(usize, (f64, f64))> {
+        self.contours
+            .iter()
+            .enumerate()
+            .flat_map(|(i, contour)| {
+                contour.self_intersections().into_iter().map(move |pt| (i, pt))
+            })
+            .collect()
+    }
+}
+
+/// An axis-aligned bounding box.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bounds {
+    /// The smallest x coordinate.
+    pub x_min: f64,
+    /// The smallest y coordinate.
+    pub y_min: f64,
+    /// The largest x coordinate.
+    pub x_max: f64,
+    /// The largest y coordinate.
+    pub y_max: f64,
+}
+
+impl Bounds {
+    fn point(pt: (f64, f64)) -> Self {
+        Bounds { x_min: pt.0, y_min: pt.1, x_max: pt.0, y_max: pt.1 }
+    }
+
+    fn include(&mut self, pt: (f64, f64)) {
+        self.x_min = self.x_min.min(pt.0);
+        self.y_min = self.y_min.min(pt.1);
+        self.x_max = self.x_max.max(pt.0);
+        self.y_max = self.y_max.max(pt.1);
+    }
+}
+
+impl Contour {
+    /// Returns the bounding box of the contour's outline, or `None` if the
+    /// contour is empty or has an invalid point sequence.
+    ///
+    /// The bounding box is exact: it encloses the curves themselves, which
+    /// may be smaller than the box around all of their points.
+    pub fn bounds(&self) -> Option<Bounds> {
+        let mut pen = BoundsPen::default();
+        draw_contour(self, AffineTransform::identity(), &mut pen)?;
+        pen.bounds
+    }
+
+    /// Returns the length of the contour's outline, or `None` if the contour
+    /// has an invalid point sequence.
+    ///
+    /// The length of a closed contour includes the closing segment back to its
+    /// start. Curves are measured by adaptive subdivision, to well within a
+    /// thousandth of a unit.
+    pub fn length(&self) -> Option<f64> {
+        let mut pen = SegmentPen::default();
+        draw_contour(self, AffineTransform::identity(), &mut pen)?;
+        Some(pen.paths.iter().flat_map(|path| &path.segments).map(|s| segment_length(s, 0)).sum())
+    }
+
+    /// Returns the centroid (center of mass) of the area enclosed by the
+    /// contour, or `None` if the contour is open, encloses no area, or has an
+    /// invalid point sequence.
+    ///
+    /// The centroid is computed exactly, curves included.
+    pub fn centroid(&self) -> Option<(f64, f64)> {
+        let mut pen = SegmentPen::default();
+        draw_contour(self, AffineTransform::identity(), &mut pen)?;
+        pen.centroid()
+    }
+}
+
+impl Glyph {
+    /// Returns the bounding box of the glyph's outline, or `None` if the
+    /// outline is empty.
+    ///
+    /// If `layer` is `Some`, components are decomposed using the base glyphs
+    /// from `layer`, as in [`Glyph::draw`]; otherwise they are ignored.
+    ///
+    /// Returns an error if a contour has an invalid point sequence, or, when
+    /// decomposing, if a base glyph is missing or components form a cycle.
+    pub fn bounds(&self, layer: Option<&Layer>) -> Result<Option<Bounds>, DrawError> {
+        let mut pen = BoundsPen::default();
+        self.draw(&mut pen, layer)?;
+        Ok(pen.bounds)
+    }
+
+    /// Returns the left and right sidebearings of the glyph, or `None` if
+    /// the outline is empty.
+    ///
+    /// The left sidebearing is the distance from the origin to the left edge
+    /// of the glyph's [bounds], and the right sidebearing the distance from
+    /// its right edge to the advance width. Either is negative if the outline
+    /// extends past the origin or the advance width respectively.
+    ///
+    /// [bounds]: Glyph::bounds
+    pub fn sidebearings(&self, layer: Option<&Layer>) -> Result<Option<(f64, f64)>, DrawError> {
+        Ok(self.bounds(layer)?.map(|b| (b.x_min, self.width - b.x_max)))
+    }
+
+    /// Returns the centroid (center of mass) of the area enclosed by the
+    /// glyph's closed contours, or `None` if they enclose no area.
+    ///
+    /// Components are decomposed or ignored as in [`Glyph::bounds`], and open
+    /// contours are ignored. Counters are subtracted from the glyph's area as
+    /// long as they run in the opposite direction to the contours around them,
+    /// as the UFO specification requires; overlapping contours running in the
+    /// same direction are counted twice.
+    ///
+    /// Returns an error if a contour has an invalid point sequence, or, when
+    /// decomposing, if a base glyph is missing or components form a cycle.
+    pub fn centroid(&self, layer: Option<&Layer>) -> Result<Option<(f64, f64)>, DrawError> {
+        let mut pen = SegmentPen::default();
+        self.draw(&mut pen, layer)?;
+        Ok(pen.centroid())
+    }
+}
+
+/// A pen that records each contour drawn into it as a list of bézier segments.
+#[derive(Default)]
+struct SegmentPen {
+    paths: Vec<SegmentPath>,
+}
+
+/// A contour as a list of segments, each given by its control points,
+/// starting with the on-curve point the segment starts from.
+struct SegmentPath {
+    start: (f64, f64),
+    segments: Vec<Vec<(f64, f64)>>,
+    closed: bool,
+}
+
+impl SegmentPen {
+    fn path(&mut self) -> &mut SegmentPath {
+        self.paths.last_mut().expect("segments always follow a move")
+    }
+
+    fn push(&mut self, points: &[(f64, f64)]) {
+        let path = self.path();
+        let current = path.segments.last().map_or(path.start, |s| s[s.len() - 1]);
+        path.segments.push(std::iter::once(current).chain(points.iter().copied()).collect());
+    }
+
+    /// The centroid of the area enclosed by the closed paths.
+    fn centroid(&self) -> Option<(f64, f64)> {
+        let (mut area, mut x, mut y) = (0.0, 0.0, 0.0);
+        for segment in self.paths.iter().filter(|p| p.closed).flat_map(|p| &p.segments) {
+            let moments = segment_moments(segment);
+            area += moments.0;
+            x += moments.1;
+            y += moments.2;
+        }
+        (area.abs() >= MIN_AREA).then(|| (x / area, y / area))
+    }
+}
+
+impl Pen for SegmentPen {
+    fn move_to(&mut self, pt: (f64, f64)) {
+        self.paths.push(SegmentPath { start: pt, segments: Vec::new(), closed: false });
+    }
+
+    fn line_to(&mut self, pt: (f64, f64)) {
+        self.push(&[pt]);
+    }
+
+    fn curve_to(&mut self, c1: (f64, f64), c2: (f64, f64), pt: (f64, f64)) {
+        self.push(&[c1, c2, pt]);
+    }
+
+    fn qcurve_to(&mut self, points: &[(f64, f64)]) {
+        for_each_quad(points, |off, end| self.push(&[off, end]));
+    }
+
+    fn close_path(&mut self) {
+        let path = self.path();
+        let current = path.segments.last().map_or(path.start, |s| s[s.len() - 1]);
+        if current != path.start {
+            let start = path.start;
+            self.push(&[start]);
+        }
+        self.path().closed = true;
+    }
+
+    fn end_path(&mut self) {}
+
+    fn add_component(&mut self, _base: &Name, _transform: AffineTransform) {}
+}
+
+/// Splits a run of TrueType quadratic curves, as passed to [`Pen::qcurve_to`],
+/// into single quadratic segments at the implied on-curve points, calling `f`
+/// with the off-curve and end point of each.
+fn for_each_quad(points: &[(f64, f64)], mut f: impl FnMut((f64, f64), (f64, f64))) {
+    let (&end, offs) = points.split_last().expect("qcurve_to always gets points");
+    for (i, &off) in offs.iter().enumerate() {
+        let next = match offs.get(i + 1) {
+            Some(next) => ((off.0 + next.0) / 2.0, (off.1 + next.1) / 2.0),
+            None => end,
+        };
+        f(off, next);
+    }
+}
+
+/// Evaluates the bézier curve with the given control points at `t`.
+fn bezier_point(points: &[(f64, f64)], t: f64) -> (f64, f64) {
+    let mut points = points.to_vec();
+    while points.len() > 1 {
+        for i in 0..points.len() - 1 {
+            let (a, b) = (points[i], points[i + 1]);
+            points[i] = (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t);
+        }
+        points.pop();
+    }
+    points[0]
+}
+
+/// Evaluates the derivative of the bézier curve with the given control points at `t`.
+fn bezier_derivative(points: &[(f64, f64)], t: f64) -> (f64, f64) {
+    let degree = (points.len() - 1) as f64;
+    let deltas: Vec<_> = points
+        .windows(2)
+        .map(|w| (degree * (w[1].0 - w[0].0), degree * (w[1].1 - w[0].1)))
+        .collect();
+    bezier_point(&deltas, t)
+}
+
+fn distance(a: (f64, f64), b: (f64, f64)) -> f64 {
+    (b.0 - a.0).hypot(b.1 - a.1)
+}
+
+/// Returns the length of a bézier segment.
+///
+/// The segment is halved until its control polygon is nearly as short as its
+/// chord, at which point the two are combined into an estimate of the length
+/// following Gravesen's method.
+fn segment_length(points: &[(f64, f64)], depth: u32) -> f64 {
+    let chord = distance(points[0], points[points.len() - 1]);
+    let polygon: f64 = points.windows(2).map(|w| distance(w[0], w[1])).sum();
+    if polygon - chord <= LENGTH_TOLERANCE || depth == MAX_LENGTH_DEPTH {
+        let degree = (points.len() - 1) as f64;
+        return (2.0 * chord + (degree - 1.0) * polygon) / (degree + 1.0);
+    }
+    // de casteljau subdivision at t = 0.5
+    let mut left = Vec::with_capacity(points.len());
+    let mut right = Vec::with_capacity(points.len());
+    let mut level = points.to_vec();
+    while !level.is_empty() {
+        left.push(level[0]);
+        right.push(level[level.len() - 1]);
+        level =
+            level.windows(2).map(|w| ((w[0].0 + w[1].0) / 2.0, (w[0].1 + w[1].1) / 2.0)).collect();
+    }
+    right.reverse();
+    segment_length(&left, depth + 1) + segment_length(&right, depth + 1)
+}
+
+/// Returns the contributions of a bézier segment of a closed path to the
+/// enclosed signed area, and to the area's first moments about the y and x axes.
+///
+/// These are the line integrals of Green's theorem, ½∮(x dy − y dx),
+/// ⅓∮x(x dy − y dx) and ⅓∮y(x dy − y dx), evaluated by quadrature.
+fn segment_moments(points: &[(f64, f64)]) -> (f64, f64, f64) {
+    let (mut area, mut x_moment, mut y_moment) = (0.0, 0.0, 0.0);
+    for (t, weight) in GAUSS_LEGENDRE {
+        let (x, y) = bezier_point(points, t);
+        let (dx, dy) = bezier_derivative(points, t);
+        let cross = weight * (x * dy - y * dx);
+        area += cross / 2.0;
+        x_moment += x * cross / 3.0;
+        y_moment += y * cross / 3.0;
+    }
+    (area, x_moment, y_moment)
+}
+
+/// The kinds of curves used by a glyph's contours, as returned by
+/// [`Glyph::curve_types`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CurveTypeSummary {
+    /// The contours have no curves, only straight lines (or there are no contours).
+    NoCurves,
+    /// All curves are cubic.
+    Cubic,
+    /// All curves are quadratic.
+    Quadratic,
+    /// The contours use both cubic and quadratic curves.
+    Mixed,
+}
+
+impl Glyph {
+    /// Returns whether the glyph's contours use cubic curves, quadratic curves,
+    /// or both.
+    ///
+    /// Curves are classified by their point types, as written in the source:
+    /// `curve` points are cubic (even when preceded by a single off-curve
+    /// point) and `qcurve` points are quadratic, as are contours made only of
+    /// off-curve points. Components are not taken into account.
+    pub fn curve_types(&self) -> CurveTypeSummary {
+        let mut cubic = false;
+        let mut quadratic = false;
+        for contour in &self.contours {
+            let points = &contour.points;
+            cubic |= points.iter().any(|p| p.typ == PointType::Curve);
+            quadratic |= points.iter().any(|p| p.typ == PointType::QCurve)
+                || (!points.is_empty() && points.iter().all(|p| p.typ == PointType::OffCurve));
+        }
+        match (cubic, quadratic) {
+            (false, false) => CurveTypeSummary::NoCurves,
+            (true, false) => CurveTypeSummary::Cubic,
+            (false, true) => CurveTypeSummary::Quadratic,
+            (true, true) => CurveTypeSummary::Mixed,
+        }
+    }
+}
+
+/// A pen that computes the exact bounding box of what is drawn into it.
+#[derive(Default)]
+struct BoundsPen {
+    bounds: Option<Bounds>,
+    current: (f64, f64),
+}
+
+impl BoundsPen {
+    fn include(&mut self, pt: (f64, f64)) {
+        match self.bounds.as_mut() {
+            Some(bounds) => bounds.include(pt),
+            None => self.bounds = Some(Bounds::point(pt)),
+        }
+    }
+
+    fn quad_to(&mut self, c: (f64, f64), pt: (f64, f64)) {
+        let p0 = self.current;
+        let eval = |t: f64| {
+            let mt = 1.0 - t;
+            let (a, b, d) = (mt * mt, 2.0 * mt * t, t * t);
+            (a * p0.0 + b * c.0 + d * pt.0, a * p0.1 + b * c.1 + d * pt.1)
+        };
+        for (p, q, r) in [(p0.0, c.0, pt.0), (p0.1, c.1, pt.1)] {
+            // the derivative is linear in t
+            let denom = p - 2.0 * q + r;
+            if denom != 0.0 {
+                let t = (p - q) / denom;
+                if 0.0 < t && t < 1.0 {
+                    self.include(eval(t));
+                }
+            }
+        }
+        self.include(pt);
+        self.current = pt;
+    }
+}
+
+impl Pen for BoundsPen {
+    fn move_to(&mut self, pt: (f64, f64)) {
+        self.include(pt);
+        self.current = pt;
+    }
+
+    fn line_to(&mut self, pt: (f64, f64)) {
+        self.include(pt);
+        self.current = pt;
+    }
+
+    fn curve_to(&mut self, c1: (f64, f64), c2: (f64, f64), pt: (f64, f64)) {
+        let p0 = self.current;
+        let eval = |t: f64| {
+            let mt = 1.0 - t;
+            let (a, b, c, d) = (mt * mt * mt, 3.0 * mt * mt * t, 3.0 * mt * t * t, t * t * t);
+            (a * p0.0 + b * c1.0 + c * c2.0 + d * pt.0, a * p0.1 + b * c1.1 + c * c2.1 + d * pt.1)
+        };
+        for (p, q, r, s) in [(p0.0, c1.0, c2.0, pt.0), (p0.1, c1.1, c2.1, pt.1)] {
+            // the roots of the derivative, a quadratic in t
+            let a = -p + 3.0 * q - 3.0 * r + s;
+            let b = 2.0 * (p - 2.0 * q + r);
+            let c = q - p;
+            let roots = if a.abs() < 1e-12 {
+                if b == 0.0 {
+                    [None, None]
+                } else {
+                    [Some(-c / b), None]
+                }
+            } else {
+                let disc = b * b - 4.0 * a * c;
+                if disc < 0.0 {
+                    [None, None]
+                } else {
+                    let sqrt = disc.sqrt();
+                    [Some((-b + sqrt) / (2.0 * a)), Some((-b - sqrt) / (2.0 * a))]
+                }
+            };
+            for t in roots.into_iter().flatten() {
+                if 0.0 < t && t < 1.0 {
+                    self.include(eval(t));
+                }
+            }
+        }
+        self.include(pt);
+        self.current = pt;
+    }
+
+    fn qcurve_to(&mut self, points: &[(f64, f64)]) {
+        for_each_quad(points, |off, end| self.quad_to(off, end));
+    }
+
+    fn close_path(&mut self) {}
+
+    fn end_path(&mut self) {}
+
+    fn add_component(&mut self, _base: &Name, _transform: AffineTransform) {}
+}
+
+/// A pen that flattens a single contour into a polyline.
+#[derive(Default)]
+struct Flattener {
+    points: Vec<(f64, f64)>,
+    closed: bool,
+}
+
+impl Flattener {
+    fn current(&self) -> (f64, f64) {
+        *self.points.last().expect("segments always follow a move")
+    }
+
+    fn push(&mut self, pt: (f64, f64)) {
+        // zero-length lines would make neighbouring lines look non-adjacent
+        if self.points.last() != Some(&pt) {
+            self.points.push(pt);
+        }
+    }
+
+    fn quad_to(&mut self, c: (f64, f64), pt: (f64, f64)) {
+        let p0 = self.current();
+        for step in 1..=CURVE_STEPS {
+            let t = step as f64 / CURVE_STEPS as f64;
+            let mt = 1.0 - t;
+            let (a, b, d) = (mt * mt, 2.0 * mt * t, t * t);
+            self.push((a * p0.0 + b * c.0 + d * pt.0, a * p0.1 + b * c.1 + d * pt.1));
+        }
+    }
+}
+
+impl Pen for Flattener {
+    fn move_to(&mut self, pt: (f64, f64)) {
+        self.points.push(pt);
+    }
+
+    fn line_to(&mut self, pt: (f64, f64)) {
+        self.push(pt);
+    }
+
+    fn curve_to(&mut self, c1: (f64, f64), c2: (f64, f64), pt: (f64, f64)) {
+        let p0 = self.current();
+        for step in 1..=CURVE_STEPS {
+            let t = step as f64 / CURVE_STEPS as f64;
+            let mt = 1.0 - t;
+            let (a, b, c, d) = (mt * mt * mt, 3.0 * mt * mt * t, 3.0 * mt * t * t, t * t * t);
+            self.push((
+                a * p0.0 + b * c1.0 + c * c2.0 + d * pt.0,
+                a * p0.1 + b * c1.1 + c * c2.1 + d * pt.1,
+            ));
+        }
+    }
+
+    fn qcurve_to(&mut self, points: &[(f64, f64)]) {
+        for_each_quad(points, |off, end| self.quad_to(off, end));
+    }
+
+    fn close_path(&mut self) {
+        if self.points.len() > 1 && self.points.first() == self.points.last() {
+            self.points.pop();
+        }
+        self.closed = true;
+    }
+
+    fn end_path(&mut self) {}
+
+    fn add_component(&mut self, _base: &Name, _transform: AffineTransform) {}
+}
+
+/// A line of a polyline, with its index and bounding box.
+struct Edge {
+    index: usize,
+    start: (f64, f64),
+    end: (f64, f64),
+    min: (f64, f64),
+    max: (f64, f64),
+}
+
+/// Finds the points where non-adjacent lines of the polyline intersect.
+///
+/// Lines are swept from left to right, so that only lines whose bounding boxes
+/// overlap are tested against each other.
+fn polyline_intersections(points: &[(f64, f64)], closed: bool) -> Vec<(f64, f64)> {
+    let count = if closed { points.len() } else { points.len().saturating_sub(1) };
+    if count < 3 {
+        return Vec::new();
+    }
+    let mut edges: Vec<Edge> = (0..count)
+        .map(|index| {
+            let (start, end) = (points[index], points[(index + 1) % points.len()]);
+            Edge {
+                index,
+                start,
+                end,
+                min: (start.0.min(end.0), start.1.min(end.1)),
+                max: (start.0.max(end.0), start.1.max(end.1)),
+            }
+        })
+        .collect();
+    edges.sort_by(|a, b| a.min.0.total_cmp(&b.min.0));
+
+    let adjacent = |a: usize, b: usize| {
+        let (lo, hi) = (a.min(b), a.max(b));
+        hi - lo == 1 || (closed && lo == 0 && hi == count - 1)
+    };
+    let mut found: Vec<(f64, f64)> = Vec::new();
+    let mut active: Vec<&Edge> = Vec::new();
+    for edge in &edges {
+        active.retain(|other| other.max.0 >= edge.min.0);
+        for other in &active {
+            if other.max.1 < edge.min.1 || other.min.1 > edge.max.1 {
+                continue;
+            }
+            if adjacent(edge.index, other.index) {
+                continue;
+            }
+            if let Some(pt) = line_intersection(edge, other) {
+                let is_new = found.iter().all(|seen| {
+                    (seen.0 - pt.0).abs() > MERGE_DISTANCE || (seen.1 - pt.1).abs() > MERGE_DISTANCE
+                });
+                if is_new {
+                    found.push(pt);
+                }
+            }
+        }
+        active.push(edge);
+    }
+    found
+}
+
+/// Returns a point where the two lines meet, if they do.
+fn line_intersection(a: &Edge, b: &Edge) -> Option<(f64, f64)> {
+    let cross = |o: (f64, f64), p: (f64, f64), q: (f64, f64)| {
+        (p.0 - o.0) * (q.1 - o.1) - (p.1 - o.1) * (q.0 - o.0)
+    };
+    let d1 = cross(b.start, b.end, a.start);
+    let d2 = cross(b.start, b.end, a.end);
+    let d3 = cross(a.start, a.end, b.start);
+    let d4 = cross(a.start, a.end, b.end);
+
+    if d1 * d2 > 0.0 || d3 * d4 > 0.0 {
+        return None;
+    }
+    if d1 != d2 {
+        // the lines are not parallel, so they meet at a single point
+        let t = d1 / (d1 - d2);
+        return Some((
+            a.start.0 + t * (a.end.0 - a.start.0),
+            a.start.1 + t * (a.end.1 - a.start.1),
+        ));
+    }
+    // the lines are collinear: report an endpoint that lies on the other line
+    let within = |p: (f64, f64), e: &Edge| {
+        e.min.0 <= p.0 && p.0 <= e.max.0 && e.min.1 <= p.1 && p.1 <= e.max.1
+    };
+    [a.start, a.end]
+        .into_iter()
+        .find(|p| within(*p, b))
+        .or_else(|| [b.start, b.end].into_iter().find(|p| within(*p, a)))
+}
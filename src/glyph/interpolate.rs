@@ -0,0 +1,128 @@
+//! Flattening a glyph's interpolatable data into a list of numbers.
+
+use super::{Glyph, PointType};
+use crate::error::InterpolatableCoordsError;
+
+/// The FNV-1a offset basis and prime, for a hash that is stable across
+/// platforms and releases.
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+impl Glyph {
+    /// Returns the glyph's interpolatable data as a flat list of numbers, for
+    /// use with an external interpolation engine.
+    ///
+    /// The list contains, in order:
+    ///
+    /// - the advance width and height;
+    /// - the x and y coordinates of every point of every contour, in order;
+    /// - the six values of every component's transform, in the order
+    ///   `x_scale`, `xy_scale`, `yx_scale`, `y_scale`, `x_offset`, `y_offset`;
+    /// - the x and y coordinates of every anchor.
+    ///
+    /// The lists of two glyphs only line up if the glyphs have the same
+    /// [`interpolation_schema_hash`](Glyph::interpolation_schema_hash).
+    pub fn interpolatable_coords(&self) -> Vec<f64> {
+        let mut coords = Vec::with_capacity(self.interpolatable_coords_len());
+        coords.extend([self.width, self.height]);
+        for point in self.contours.iter().flat_map(|c| &c.points) {
+            coords.extend([point.x, point.y]);
+        }
+        for component in &self.components {
+            let t = component.transform;
+            coords.extend([t.x_scale, t.xy_scale, t.yx_scale, t.y_scale, t.x_offset, t.y_offset]);
+        }
+        for anchor in &self.anchors {
+            coords.extend([anchor.x, anchor.y]);
+        }
+        coords
+    }
+
+    /// Rebuilds a glyph from `template` and a list of numbers laid out as by
+    /// [`Glyph::interpolatable_coords`], such as the result of blending the
+    /// lists of several compatible masters.
+    ///
+    /// Everything that isn't interpolatable, including the glyph's name, the
+    /// point types and the component bases, is copied from `template`.
+    ///
+    /// Returns an error if `coords` doesn't have as many numbers as the
+    /// template's own list.
+    pub fn from_interpolatable_coords(
+        template: &Glyph,
+        coords: &[f64],
+    ) -> Result<Glyph, InterpolatableCoordsError> {
+        let expected = template.interpolatable_coords_len();
+        if coords.len() != expected {
+            return Err(InterpolatableCoordsError::WrongLength { expected, found: coords.len() });
+        }
+
+        let mut glyph = template.clone();
+        let mut coords = coords.iter().copied();
+        let mut next = || coords.next().expect("length was checked");
+        (glyph.width, glyph.height) = (next(), next());
+        for point in glyph.contours.iter_mut().flat_map(|c| &mut c.points) {
+            (point.x, point.y) = (next(), next());
+        }
+        for component in &mut glyph.components {
+            let t = &mut component.transform;
+            (t.x_scale, t.xy_scale, t.yx_scale, t.y_scale) = (next(), next(), next(), next());
+            (t.x_offset, t.y_offset) = (next(), next());
+        }
+        for anchor in &mut glyph.anchors {
+            (anchor.x, anchor.y) = (next(), next());
+        }
+        Ok(glyph)
+    }
+
+    /// Returns a hash of the structure of the glyph's interpolatable data.
+    ///
+    /// Two glyphs with the same hash have the same number of contours, with
+    /// the same point types, the same component bases and the same anchor
+    /// names, all in the same order, so their [interpolatable coordinates]
+    /// can be blended number by number. The hash does not depend on any
+    /// coordinates, and is stable across platforms and norad versions.
+    ///
+    /// [interpolatable coordinates]: Glyph::interpolatable_coords
+    pub fn interpolation_schema_hash(&self) -> u64 {
+        let mut hash = FNV_OFFSET_BASIS;
+        let mut write = |bytes: &[u8]| {
+            for byte in bytes {
+                hash = (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME);
+            }
+        };
+
+        write(&(self.contours.len() as u64).to_le_bytes());
+        for contour in &self.contours {
+            write(&(contour.points.len() as u64).to_le_bytes());
+            for point in &contour.points {
+                write(&[match point.typ {
+                    PointType::Move => 0,
+                    PointType::Line => 1,
+                    PointType::OffCurve => 2,
+                    PointType::Curve => 3,
+                    PointType::QCurve => 4,
+                }]);
+            }
+        }
+        // names are length-prefixed, so that consecutive names can't run together
+        write(&(self.components.len() as u64).to_le_bytes());
+        for component in &self.components {
+            write(&(component.base.len() as u64).to_le_bytes());
+            write(component.base.as_bytes());
+        }
+        write(&(self.anchors.len() as u64).to_le_bytes());
+        for anchor in &self.anchors {
+            let name = anchor.name.as_deref().unwrap_or_default();
+            write(&[u8::from(anchor.name.is_some())]);
+            write(&(name.len() as u64).to_le_bytes());
+            write(name.as_bytes());
+        }
+        hash
+    }
+
+    /// The number of values returned by [`Glyph::interpolatable_coords`].
+    fn interpolatable_coords_len(&self) -> usize {
+        let points: usize = self.contours.iter().map(|c| c.points.len()).sum();
+        2 + 2 * points + 6 * self.components.len() + 2 * self.anchors.len()
+    }
+}
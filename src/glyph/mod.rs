@@ -2,23 +2,35 @@
 
 pub mod builder;
 mod codepoints;
+mod decompose;
+mod geometry;
+mod interpolate;
 mod parse;
+mod pen;
 mod serialize;
 #[cfg(test)]
 mod tests;
 
 use std::path::{Path, PathBuf};
 
+use quick_xml::events::Event;
+
 #[cfg(feature = "kurbo")]
 use crate::error::ConvertContourError;
 
-use crate::error::{ErrorKind, GlifLoadError, GlifWriteError, StoreError};
+use crate::error::{
+    ErrorKind, GlifLoadError, GlifLoadWarning, GlifWriteError, GlyphValidationError, NamingError,
+    OpenContourError, StoreError,
+};
 use crate::name::Name;
 use crate::names::NameList;
 use crate::shared_types::PUBLIC_OBJECT_LIBS_KEY;
-use crate::{Color, Guideline, Identifier, Line, Plist, WriteOptions};
+use crate::{Color, Guideline, Identifier, Layer, Line, Plist, WriteOptions};
 
 pub use codepoints::Codepoints;
+pub use decompose::DecomposeOptions;
+pub use geometry::{Bounds, CurveTypeSummary};
+pub use pen::{GlyphPointPen, Pen, PointPen};
 
 /// A glyph, loaded from a [`.glif` file][glif].
 ///
@@ -51,6 +63,9 @@ pub struct Glyph {
     pub image: Option<Image>,
     /// Glyph library data.
     pub lib: Plist,
+    /// Unknown `<outline>` elements kept by lenient parsing, as XML events,
+    /// so that they can be written back unchanged.
+    pub(crate) unknown_outline_elements: Vec<Event<'static>>,
 }
 
 impl Glyph {
@@ -60,7 +75,7 @@ impl Glyph {
     pub fn load(path: impl AsRef<Path>) -> Result<Self, GlifLoadError> {
         let path = path.as_ref();
         let names = NameList::default();
-        Glyph::load_with_names(path, &names)
+        Glyph::load_with_names(path, &names, false).map(|(glyph, _)| glyph)
     }
 
     /// THIS IS NOT STABLE API!
@@ -69,17 +84,25 @@ impl Glyph {
     #[doc(hidden)]
     pub fn parse_raw(xml: &[u8]) -> Result<Self, GlifLoadError> {
         let names = NameList::default();
-        parse::GlifParser::from_xml(xml, Some(&names))
+        parse::GlifParser::from_xml(xml, Some(&names), false)
     }
 
     /// Attempt to load the glyph at `path`, reusing names from the `NameList`.
     ///
     /// This uses string interning to reuse allocations when a glyph name
     /// occurs multiple times (such as in components or in different layers).
-    pub(crate) fn load_with_names(path: &Path, names: &NameList) -> Result<Self, GlifLoadError> {
+    ///
+    /// If `lenient` is true, unknown elements in the outline are kept aside
+    /// rather than treated as errors, and the problems that were tolerated
+    /// are returned alongside the glyph.
+    pub(crate) fn load_with_names(
+        path: &Path,
+        names: &NameList,
+        lenient: bool,
+    ) -> Result<(Self, Vec<GlifLoadWarning>), GlifLoadError> {
         std::fs::read(path)
             .map_err(GlifLoadError::Io)
-            .and_then(|data| parse::GlifParser::from_xml(&data, Some(names)))
+            .and_then(|data| parse::GlifParser::from_xml_with_warnings(&data, Some(names), lenient))
     }
 
     #[doc(hidden)]
@@ -130,6 +153,7 @@ impl Glyph {
             contours: Vec::new(),
             image: None,
             lib: Plist::new(),
+            unknown_outline_elements: Vec::new(),
         }
     }
 
@@ -154,6 +178,27 @@ impl Glyph {
         self.components.iter().any(|x| *x.base == *basename)
     }
 
+    /// Checks the glyph for inconsistencies with `layer`, the layer containing
+    /// it, returning every problem found.
+    ///
+    /// The following are checked:
+    ///
+    /// - every component's base glyph exists in `layer`.
+    ///
+    /// Component cycles are not reported; see [`Glyph::decompose`] for those.
+    /// An empty list means no problems were found.
+    pub fn validate(&self, layer: &Layer) -> Vec<GlyphValidationError> {
+        self.components
+            .iter()
+            .enumerate()
+            .filter(|(_, component)| !layer.contains_glyph(&component.base))
+            .map(|(index, component)| GlyphValidationError::MissingComponentBase {
+                index,
+                base: component.base.clone(),
+            })
+            .collect()
+    }
+
     /// Returns an iterator over immutable [`Component`] references filtered by base glyph name.
     pub fn get_components_with_base<'b, 'a: 'b>(
         &'a self,
@@ -162,6 +207,185 @@ impl Glyph {
         self.components.iter().filter(move |x| *x.base == *basename)
     }
 
+    /// Returns a copy of this glyph with only its advance metrics interpolated.
+    ///
+    /// `self` is treated as the default master. The result's width and height
+    /// are the default's, plus the sum of each master's difference from
+    /// the default multiplied by the corresponding entry in `weights`
+    /// (as produced by a variation model). Everything else, including contours,
+    /// components and anchors, is copied from the default unchanged.
+    ///
+    /// This is appropriate for spacing-only masters, where the outlines are known
+    /// to be identical and only the metrics differ. It does no compatibility
+    /// checks at all, so it will not fail on incompatible outlines, but neither
+    /// will it interpolate them; if the outlines differ between masters, the
+    /// result's outline will simply be wrong.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `others` and `weights` have different lengths.
+    pub fn interpolate_metrics_only(&self, others: &[&Glyph], weights: &[f64]) -> Glyph {
+        assert_eq!(others.len(), weights.len(), "each master must have exactly one weight");
+        let mut result = self.clone();
+        for (other, weight) in others.iter().zip(weights) {
+            result.width += (other.width - self.width) * weight;
+            result.height += (other.height - self.height) * weight;
+        }
+        result
+    }
+
+    /// Removes contours that have the same outline as an earlier contour in the
+    /// glyph, as determined by [`Contour::is_same_outline`] with `tolerance`.
+    ///
+    /// The first of each set of duplicates is kept. Returns the number of
+    /// contours removed.
+    pub fn remove_duplicate_contours(&mut self, tolerance: f64) -> usize {
+        let mut kept: Vec<Contour> = Vec::with_capacity(self.contours.len());
+        let before = self.contours.len();
+        for contour in self.contours.drain(..) {
+            if !kept.iter().any(|other| other.is_same_outline(&contour, tolerance)) {
+                kept.push(contour);
+            }
+        }
+        self.contours = kept;
+        before - self.contours.len()
+    }
+
+    /// Reflects the glyph across the vertical line at `center_x`, so that
+    /// left becomes right.
+    ///
+    /// Contours, anchors, guidelines, the image and component transforms are
+    /// all mirrored, and contours are then [reversed](Contour::reverse) so that
+    /// they keep their original winding direction. The contours that components
+    /// draw cannot be reversed from here, so mirrored components keep the
+    /// reflected direction of their base glyph.
+    pub fn mirror_horizontal(&mut self, center_x: f64) {
+        let transform =
+            AffineTransform { x_scale: -1.0, x_offset: 2.0 * center_x, ..Default::default() };
+        self.mirror(transform);
+        for guideline in &mut self.guidelines {
+            guideline.line = match guideline.line {
+                Line::Vertical(x) => Line::Vertical(2.0 * center_x - x),
+                Line::Horizontal(y) => Line::Horizontal(y),
+                Line::Angle { x, y, degrees } => Line::Angle {
+                    x: 2.0 * center_x - x,
+                    y,
+                    degrees: (180.0 - degrees).rem_euclid(360.0),
+                },
+            };
+        }
+    }
+
+    /// Reflects the glyph across the horizontal line at `center_y`, so that
+    /// top becomes bottom.
+    ///
+    /// See [`Glyph::mirror_horizontal`] for which parts of the glyph are
+    /// affected.
+    pub fn mirror_vertical(&mut self, center_y: f64) {
+        let transform =
+            AffineTransform { y_scale: -1.0, y_offset: 2.0 * center_y, ..Default::default() };
+        self.mirror(transform);
+        for guideline in &mut self.guidelines {
+            guideline.line = match guideline.line {
+                Line::Vertical(x) => Line::Vertical(x),
+                Line::Horizontal(y) => Line::Horizontal(2.0 * center_y - y),
+                Line::Angle { x, y, degrees } => Line::Angle {
+                    x,
+                    y: 2.0 * center_y - y,
+                    degrees: (360.0 - degrees).rem_euclid(360.0),
+                },
+            };
+        }
+    }
+
+    /// Applies the reflection `transform` to everything but the guidelines.
+    fn mirror(&mut self, transform: AffineTransform) {
+        for contour in &mut self.contours {
+            for point in &mut contour.points {
+                point.transform(transform);
+            }
+            contour.reverse();
+        }
+        for component in &mut self.components {
+            component.transform = transform.concat(component.transform);
+        }
+        for anchor in &mut self.anchors {
+            (anchor.x, anchor.y) = transform.apply((anchor.x, anchor.y));
+        }
+        if let Some(image) = &mut self.image {
+            image.transform = transform.concat(image.transform);
+        }
+    }
+
+    /// Returns the lib of the anchor, guideline, contour, point or component
+    /// with the given `identifier`, if it exists and has a lib.
+    ///
+    /// Object libs are stored on the objects themselves rather than in the
+    /// glyph's `public.objectLibs` lib key, so they are removed along with
+    /// their objects. Entries of `public.objectLibs` that don't belong to any
+    /// object are dropped when a glyph is loaded.
+    pub fn object_lib(&self, identifier: &str) -> Option<&Plist> {
+        let anchors = self.anchors.iter().map(|a| (a.identifier(), a.lib()));
+        let guidelines = self.guidelines.iter().map(|g| (g.identifier(), g.lib()));
+        let contours = self.contours.iter().flat_map(|c| {
+            std::iter::once((c.identifier(), c.lib()))
+                .chain(c.points.iter().map(|p| (p.identifier(), p.lib())))
+        });
+        let components = self.components.iter().map(|c| (c.identifier(), c.lib()));
+        anchors
+            .chain(guidelines)
+            .chain(contours)
+            .chain(components)
+            .find(|(id, _)| id.map(|id| id.as_str()) == Some(identifier))
+            .and_then(|(_, lib)| lib)
+    }
+
+    /// Returns a mutable reference to the lib of the object with the given
+    /// `identifier`, if it exists and has a lib.
+    ///
+    /// See [`Glyph::object_lib`] for the objects that are searched.
+    pub fn object_lib_mut(&mut self, identifier: &str) -> Option<&mut Plist> {
+        self.object_lib_slot(identifier)?.as_mut()
+    }
+
+    /// Replaces the lib of the object with the given `identifier`, returning
+    /// the previous lib, if any.
+    ///
+    /// Returns an error if no object in the glyph has that identifier.
+    pub fn replace_object_lib(
+        &mut self,
+        identifier: &str,
+        lib: Plist,
+    ) -> Result<Option<Plist>, NamingError> {
+        let slot = self
+            .object_lib_slot(identifier)
+            .ok_or_else(|| NamingError::Missing(identifier.into()))?;
+        Ok(slot.replace(lib))
+    }
+
+    /// Takes the lib of the object with the given `identifier`, leaving the
+    /// object without one.
+    pub fn take_object_lib(&mut self, identifier: &str) -> Option<Plist> {
+        self.object_lib_slot(identifier)?.take()
+    }
+
+    /// Returns the lib field of the object with the given identifier.
+    fn object_lib_slot(&mut self, identifier: &str) -> Option<&mut Option<Plist>> {
+        let anchors = self.anchors.iter_mut().map(|a| (a.identifier.as_ref(), &mut a.lib));
+        let guidelines = self.guidelines.iter_mut().map(|g| (g.identifier.as_ref(), &mut g.lib));
+        let contours = self.contours.iter_mut().flat_map(|c| {
+            std::iter::once((c.identifier.as_ref(), &mut c.lib))
+                .chain(c.points.iter_mut().map(|p| (p.identifier.as_ref(), &mut p.lib)))
+        });
+        let components = self.components.iter_mut().map(|c| (c.identifier.as_ref(), &mut c.lib));
+        anchors
+            .chain(guidelines)
+            .chain(contours)
+            .chain(components)
+            .find(|(id, _)| id.map(|id| id.as_str()) == Some(identifier))
+            .map(|(_, lib)| lib)
+    }
+
     /// Move libs from the lib's `public.objectLibs` into the actual objects.
     /// The key will be removed from the glyph lib.
     fn load_object_libs(&mut self) -> Result<(), GlifLoadError> {
@@ -205,13 +429,21 @@ impl Glyph {
         Ok(())
     }
 
-    /// Dump guideline libs into a Plist.
-    fn dump_object_libs(&self) -> Plist {
+    /// Dump object libs into a Plist, keyed by the identifier of their object.
+    ///
+    /// Returns an error if two objects with a lib share an identifier, as one
+    /// of the libs would otherwise be silently dropped.
+    fn dump_object_libs(&self) -> Result<Plist, GlifWriteError> {
         let mut object_libs = Plist::default();
+        let mut duplicate = None;
 
         let mut dump_lib = |id: Option<&Identifier>, lib: &Plist| {
-            let id = id.map(|id| id.as_str().to_string());
-            object_libs.insert(id.unwrap(), plist::Value::Dictionary(lib.clone()));
+            let id = id.expect("objects with a lib always have an identifier");
+            let previous =
+                object_libs.insert(id.as_str().to_string(), plist::Value::Dictionary(lib.clone()));
+            if previous.is_some() {
+                duplicate.get_or_insert_with(|| id.clone());
+            }
         };
 
         for anchor in &self.anchors {
@@ -242,7 +474,10 @@ impl Glyph {
             }
         }
 
-        object_libs
+        match duplicate {
+            Some(id) => Err(GlifWriteError::DuplicateObjectLibIdentifier(id)),
+            None => Ok(object_libs),
+        }
     }
 }
 
@@ -305,6 +540,113 @@ impl Contour {
         self.points.first().map_or(true, |v| v.typ != PointType::Move)
     }
 
+    /// Closes the contour by connecting its last point to its first point with
+    /// a straight line.
+    ///
+    /// The leading `move` point becomes a `line` point. This does nothing if the
+    /// contour is already closed.
+    pub fn close(&mut self) {
+        if let Some(first) = self.points.first_mut() {
+            if first.typ == PointType::Move {
+                first.typ = PointType::Line;
+            }
+        }
+    }
+
+    /// Opens a closed contour at the on-curve point at `index`, preserving its
+    /// outline.
+    ///
+    /// The points are rotated so that the contour starts at `index`, which
+    /// becomes a `move` point, and a copy of that point with its original type
+    /// is appended to end the segment which previously led into it. The copy
+    /// has no identifier or lib, so that identifiers remain unique.
+    ///
+    /// Returns an error if the contour is already open or if the point at
+    /// `index` is an off-curve point.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn open_at(&mut self, index: usize) -> Result<(), OpenContourError> {
+        if !self.is_closed() {
+            return Err(OpenContourError::AlreadyOpen);
+        }
+        let point = &self.points[index];
+        if point.typ == PointType::OffCurve {
+            return Err(OpenContourError::OffCurve(index));
+        }
+
+        let end = ContourPoint::new(
+            point.x,
+            point.y,
+            point.typ.clone(),
+            false,
+            point.name.clone(),
+            None,
+            None,
+        );
+        self.points.rotate_left(index);
+        let start = &mut self.points[0];
+        start.typ = PointType::Move;
+        start.smooth = false;
+        self.points.push(end);
+        Ok(())
+    }
+
+    /// Reverses the direction of the contour.
+    ///
+    /// The point types are adjusted so that each on-curve point again describes
+    /// the segment leading into it. A closed contour keeps its first point; an
+    /// open contour starts at its former last point.
+    pub fn reverse(&mut self) {
+        if self.points.is_empty() {
+            return;
+        }
+        let on_curve: Vec<usize> =
+            (0..self.points.len()).filter(|&i| self.points[i].typ != PointType::OffCurve).collect();
+        // each on-curve point takes the type of the segment that starts at it,
+        // which is the type of the next on-curve point; for an open contour,
+        // the last point wraps around and becomes the move
+        let new_types: Vec<PointType> = (0..on_curve.len())
+            .map(|i| self.points[on_curve[(i + 1) % on_curve.len()]].typ.clone())
+            .collect();
+        for (&i, typ) in on_curve.iter().zip(new_types) {
+            self.points[i].typ = typ;
+        }
+        self.points.reverse();
+        if self.is_closed() {
+            self.points.rotate_right(1);
+        }
+    }
+
+    /// Whether this contour describes the same outline as `other`.
+    ///
+    /// The contours match if they have the same number of points and each
+    /// point has the same type as its counterpart, with coordinates that differ
+    /// by at most `tolerance`. Closed contours are compared regardless of which
+    /// point they start at, as the starting point of a closed contour does not
+    /// change its outline. The direction must match: a contour and its reverse
+    /// are not considered the same, since they affect the fill differently.
+    ///
+    /// Point names, smoothness, identifiers and libs are ignored.
+    pub fn is_same_outline(&self, other: &Contour, tolerance: f64) -> bool {
+        let len = self.points.len();
+        if len != other.points.len() || self.is_closed() != other.is_closed() {
+            return false;
+        }
+        let points_match = |offset: usize| {
+            self.points.iter().enumerate().all(|(i, a)| {
+                let b = &other.points[(i + offset) % len];
+                a.typ == b.typ && (a.x - b.x).abs() <= tolerance && (a.y - b.y).abs() <= tolerance
+            })
+        };
+        if self.is_closed() {
+            len == 0 || (0..len).any(points_match)
+        } else {
+            points_match(0)
+        }
+    }
+
     /// Converts the `Contour` to a [`kurbo::BezPath`].
     #[cfg(feature = "kurbo")]
     pub fn to_kurbo(&self) -> Result<kurbo::BezPath, ConvertContourError> {
@@ -455,6 +797,57 @@ pub struct AffineTransform {
     pub y_offset: f64,
 }
 
+/// An [`AffineTransform`] broken down into human-readable parts, as returned by
+/// [`AffineTransform::decompose`].
+///
+/// The original transform is obtained by skewing, then scaling, then rotating,
+/// then translating; converting back with [`AffineTransform::from`] does exactly
+/// that. All angles are in degrees, counter-clockwise.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransformComponents {
+    /// Translation along the x axis.
+    pub translate_x: f64,
+    /// Translation along the y axis.
+    pub translate_y: f64,
+    /// Rotation angle, in degrees.
+    pub rotation: f64,
+    /// Scale factor along the x axis. Negative for reflections.
+    pub scale_x: f64,
+    /// Scale factor along the y axis.
+    pub scale_y: f64,
+    /// Skew angle along the x axis, in degrees.
+    pub skew_x: f64,
+    /// Skew angle along the y axis, in degrees.
+    pub skew_y: f64,
+}
+
+impl TransformComponents {
+    /// Returns `true` if the transform mirrors the outline it is applied to.
+    pub fn is_reflection(&self) -> bool {
+        self.scale_x * self.scale_y < 0.0
+    }
+}
+
+impl From<TransformComponents> for AffineTransform {
+    fn from(c: TransformComponents) -> Self {
+        let (sin, cos) = c.rotation.to_radians().sin_cos();
+        let rotate_scale = AffineTransform {
+            x_scale: cos * c.scale_x,
+            xy_scale: sin * c.scale_x,
+            yx_scale: -sin * c.scale_y,
+            y_scale: cos * c.scale_y,
+            x_offset: c.translate_x,
+            y_offset: c.translate_y,
+        };
+        let skew = AffineTransform {
+            xy_scale: c.skew_y.to_radians().tan(),
+            yx_scale: c.skew_x.to_radians().tan(),
+            ..AffineTransform::identity()
+        };
+        rotate_scale.concat(skew)
+    }
+}
+
 impl Anchor {
     /// Returns a new [`Anchor`] given `x` and `y` coordinate values.
     pub fn new(
@@ -704,6 +1097,77 @@ impl AffineTransform {
             y_offset: 0.,
         }
     }
+
+    /// Breaks the transform down into translation, rotation, scale and skew,
+    /// for display in a user interface.
+    ///
+    /// The decomposition puts as much as possible into rotation and scale: skew
+    /// is only needed for transforms that do not preserve right angles, and at
+    /// most one of the two skew angles is non-zero.
+    ///
+    /// A transform with a negative determinant mirrors outlines. Such a
+    /// reflection is always expressed as a negative [`scale_x`], rather than
+    /// as a rotation by 180° combined with a negative y scale, which is
+    /// equivalent but harder to read.
+    ///
+    /// [`scale_x`]: TransformComponents::scale_x
+    pub fn decompose(&self) -> TransformComponents {
+        let reflection = self.determinant() < 0.0;
+        // Decompose the transform mirrored along x, so the determinant is positive,
+        // then move the mirroring into the x scale.
+        let sign = if reflection { -1.0 } else { 1.0 };
+        let (a, b, c, d) = (self.x_scale * sign, self.xy_scale * sign, self.yx_scale, self.y_scale);
+        let delta = a * d - b * c;
+
+        let (rotation, scale_x, scale_y, skew_x, skew_y) = if a != 0.0 || b != 0.0 {
+            let r = a.hypot(b);
+            (b.atan2(a), r, delta / r, ((a * c + b * d) / (r * r)).atan(), 0.0)
+        } else if c != 0.0 || d != 0.0 {
+            let s = c.hypot(d);
+            ((-c).atan2(d), delta / s, s, 0.0, ((a * c + b * d) / (s * s)).atan())
+        } else {
+            (0.0, 0.0, 0.0, 0.0, 0.0)
+        };
+
+        TransformComponents {
+            translate_x: self.x_offset,
+            translate_y: self.y_offset,
+            rotation: rotation.to_degrees(),
+            scale_x: scale_x * sign,
+            scale_y,
+            skew_x: skew_x.to_degrees() * sign,
+            skew_y: skew_y.to_degrees() * sign,
+        }
+    }
+
+    /// Returns the determinant of the linear part of the transform.
+    fn determinant(&self) -> f64 {
+        self.x_scale * self.y_scale - self.xy_scale * self.yx_scale
+    }
+
+    /// Returns the transformation that applies `inner` first, then `self`.
+    pub(crate) fn concat(self, inner: AffineTransform) -> AffineTransform {
+        AffineTransform {
+            x_scale: self.x_scale * inner.x_scale + self.yx_scale * inner.xy_scale,
+            xy_scale: self.xy_scale * inner.x_scale + self.y_scale * inner.xy_scale,
+            yx_scale: self.x_scale * inner.yx_scale + self.yx_scale * inner.y_scale,
+            y_scale: self.xy_scale * inner.yx_scale + self.y_scale * inner.y_scale,
+            x_offset: self.x_scale * inner.x_offset
+                + self.yx_scale * inner.y_offset
+                + self.x_offset,
+            y_offset: self.xy_scale * inner.x_offset
+                + self.y_scale * inner.y_offset
+                + self.y_offset,
+        }
+    }
+
+    /// Applies the transformation to the point `(x, y)`.
+    pub(crate) fn apply(self, (x, y): (f64, f64)) -> (f64, f64) {
+        (
+            self.x_scale * x + self.yx_scale * y + self.x_offset,
+            self.xy_scale * x + self.y_scale * y + self.y_offset,
+        )
+    }
 }
 
 impl std::default::Default for AffineTransform {
@@ -737,7 +1201,7 @@ impl Image {
         if file_name.is_absolute() {
             return Err(StoreError::PathIsAbsolute);
         }
-        if file_name.parent().map_or(false, |p| !p.as_os_str().is_empty()) {
+        if file_name.parent().is_some_and(|p| !p.as_os_str().is_empty()) {
             return Err(StoreError::Subdir);
         }
         Ok(Self { file_name, color, transform })
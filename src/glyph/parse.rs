@@ -3,7 +3,7 @@ use std::convert::TryFrom;
 use std::path::PathBuf;
 
 use super::*;
-use crate::error::{ErrorKind, GlifLoadError};
+use crate::error::{ErrorKind, GlifLoadError, GlifLoadWarning};
 use crate::glyph::builder::OutlineBuilder;
 use crate::names::NameList;
 
@@ -14,7 +14,7 @@ use quick_xml::{
 
 #[cfg(test)]
 pub(crate) fn parse_glyph(xml: &[u8]) -> Result<Glyph, GlifLoadError> {
-    GlifParser::from_xml(xml, None)
+    GlifParser::from_xml(xml, None, false)
 }
 
 // major, minor
@@ -29,20 +29,42 @@ pub(crate) struct GlifParser<'names> {
     seen_identifiers: HashSet<Identifier>,
     /// Optional set of glyph names to be reused between glyphs.
     names: Option<&'names NameList>,
+    /// Whether to skip unknown outline elements instead of erroring.
+    lenient: bool,
+    /// Problems tolerated because of `lenient`.
+    warnings: Vec<GlifLoadWarning>,
 }
 
 impl<'names> GlifParser<'names> {
     pub(crate) fn from_xml(
         xml: &[u8],
         names: Option<&'names NameList>,
+        lenient: bool,
     ) -> Result<Glyph, GlifLoadError> {
+        Self::from_xml_with_warnings(xml, names, lenient).map(|(glyph, _)| glyph)
+    }
+
+    /// Like [`GlifParser::from_xml`], but also returns the problems that were
+    /// tolerated in lenient mode.
+    pub(crate) fn from_xml_with_warnings(
+        xml: &[u8],
+        names: Option<&'names NameList>,
+        lenient: bool,
+    ) -> Result<(Glyph, Vec<GlifLoadWarning>), GlifLoadError> {
         let mut reader = Reader::from_reader(xml);
         let mut buf = Vec::new();
         reader.trim_text(true);
 
         let (name, version) = start(&mut reader, &mut buf, names)?;
         let glyph = Glyph::new_impl(name);
-        let parser = GlifParser { glyph, seen_identifiers: Default::default(), names, version };
+        let parser = GlifParser {
+            glyph,
+            seen_identifiers: Default::default(),
+            names,
+            version,
+            lenient,
+            warnings: Vec::new(),
+        };
         parser.parse_body(&mut reader, xml, &mut buf)
     }
 
@@ -51,7 +73,7 @@ impl<'names> GlifParser<'names> {
         reader: &mut Reader<&[u8]>,
         raw_xml: &[u8],
         buf: &mut Vec<u8>,
-    ) -> Result<Glyph, GlifLoadError> {
+    ) -> Result<(Glyph, Vec<GlifLoadWarning>), GlifLoadError> {
         let mut seen_advance = false;
         let mut seen_lib = false;
         let mut seen_outline = false;
@@ -123,7 +145,7 @@ impl<'names> GlifParser<'names> {
         }
 
         self.glyph.load_object_libs()?;
-        Ok(self.glyph)
+        Ok((self.glyph, self.warnings))
     }
 
     fn parse_outline(
@@ -145,14 +167,21 @@ impl<'names> GlifParser<'names> {
                         b"contour" => {
                             self.parse_contour(start, reader, &mut new_buf, &mut outline_builder)?
                         }
-                        _other => return Err(ErrorKind::UnexpectedElement.into()),
+                        _other if self.lenient => {
+                            self.keep_unknown_element(start.into_owned(), reader, &mut new_buf)?
+                        }
+                        other => return Err(unexpected_outline_element(other, reader)),
                     }
                 }
                 Event::Empty(start) => {
                     match start.name().as_ref() {
                         b"contour" => (), // Empty contours are meaningless.
                         b"component" => self.parse_component(start, &mut outline_builder)?,
-                        _other => return Err(ErrorKind::UnexpectedElement.into()),
+                        _other if self.lenient => self
+                            .glyph
+                            .unknown_outline_elements
+                            .push(Event::Empty(start.into_owned())),
+                        other => return Err(unexpected_outline_element(other, reader)),
                     }
                 }
                 Event::End(ref end) if end.name().as_ref() == b"outline" => break,
@@ -194,17 +223,52 @@ impl<'names> GlifParser<'names> {
         Ok(())
     }
 
-    fn parse_identifier(&mut self, value: &str) -> Result<Identifier, GlifLoadError> {
+    /// Parses an identifier attribute.
+    ///
+    /// In lenient mode an invalid identifier is dropped (returning `None`),
+    /// so that the object carrying it is still loaded.
+    fn parse_identifier(&mut self, value: &str) -> Result<Option<Identifier>, GlifLoadError> {
         if self.version == VERSION_1 {
             return Err(ErrorKind::UnexpectedV1Attribute("identifier").into());
         }
 
-        let id =
-            Identifier::new(value).map_err(|_| GlifLoadError::Parse(ErrorKind::BadIdentifier))?;
+        let id = match Identifier::new(value) {
+            Ok(id) => id,
+            Err(_) if self.lenient => {
+                self.warnings.push(GlifLoadWarning::InvalidIdentifier(value.to_string()));
+                return Ok(None);
+            }
+            Err(_) => return Err(ErrorKind::BadIdentifier.into()),
+        };
         if !self.seen_identifiers.insert(id.clone()) {
             return Err(ErrorKind::DuplicateIdentifier.into());
         }
-        Ok(id)
+        Ok(Some(id))
+    }
+
+    /// Stores the events of an unknown outline element, starting at `start`,
+    /// so that it can be written back when the glyph is saved.
+    fn keep_unknown_element(
+        &mut self,
+        start: BytesStart<'static>,
+        reader: &mut Reader<&[u8]>,
+        buf: &mut Vec<u8>,
+    ) -> Result<(), GlifLoadError> {
+        let elements = &mut self.glyph.unknown_outline_elements;
+        elements.push(Event::Start(start));
+        let mut depth = 1;
+        while depth > 0 {
+            let event = reader.read_event_into(buf)?.into_owned();
+            match event {
+                Event::Start(_) => depth += 1,
+                Event::End(_) => depth -= 1,
+                Event::Eof => return Err(ErrorKind::UnexpectedEof.into()),
+                _ => (),
+            }
+            elements.push(event);
+            buf.clear();
+        }
+        Ok(())
     }
 
     fn parse_contour(
@@ -222,7 +286,7 @@ impl<'names> GlifParser<'names> {
             let attr = attr?;
             let value = attr.unescape_value()?;
             match attr.key.as_ref() {
-                b"identifier" => identifier = Some(self.parse_identifier(&value)?),
+                b"identifier" => identifier = self.parse_identifier(&value)?,
                 _other => return Err(ErrorKind::UnexpectedAttribute.into()),
             }
         }
@@ -273,7 +337,7 @@ impl<'names> GlifParser<'names> {
                     base = Some(name);
                 }
                 b"identifier" => {
-                    identifier = Some(self.parse_identifier(&value)?);
+                    identifier = self.parse_identifier(&value)?;
                 }
                 _other => return Err(ErrorKind::UnexpectedComponentField.into()),
             }
@@ -365,7 +429,7 @@ impl<'names> GlifParser<'names> {
                 }
                 b"smooth" => smooth = value == "yes",
                 b"identifier" => {
-                    identifier = Some(self.parse_identifier(&value)?);
+                    identifier = self.parse_identifier(&value)?;
                 }
                 _other => return Err(ErrorKind::UnexpectedPointField.into()),
             }
@@ -387,13 +451,24 @@ impl<'names> GlifParser<'names> {
             let attr = attr?;
             match attr.key.as_ref() {
                 b"width" | b"height" => {
-                    let value = attr.unescape_value()?;
-                    let value: f64 = value.parse().map_err(|_| ErrorKind::BadNumber)?;
-                    match attr.key.as_ref() {
-                        b"width" => width = value,
-                        b"height" => height = value,
-                        _other => unreachable!(),
+                    let attribute = match attr.key.as_ref() {
+                        b"width" => "width",
+                        _ => "height",
                     };
+                    let value = attr.unescape_value()?;
+                    let mut value: f64 = value.parse().map_err(|_| ErrorKind::BadNumber)?;
+                    if !value.is_finite() {
+                        if !self.lenient {
+                            return Err(ErrorKind::BadNumber.into());
+                        }
+                        self.warnings.push(GlifLoadWarning::NonFiniteAdvance { attribute, value });
+                        value = 0.0;
+                    }
+                    if attribute == "width" {
+                        width = value;
+                    } else {
+                        height = value;
+                    }
                 }
                 _other => return Err(ErrorKind::UnexpectedAttribute.into()),
             }
@@ -442,7 +517,7 @@ impl<'names> GlifParser<'names> {
                 b"name" => name = Some(Name::new(&value).map_err(|_| ErrorKind::InvalidName)?),
                 b"color" => color = Some(value.parse().map_err(|_| ErrorKind::BadColor)?),
                 b"identifier" => {
-                    identifier = Some(self.parse_identifier(&value)?);
+                    identifier = self.parse_identifier(&value)?;
                 }
                 _other => return Err(ErrorKind::UnexpectedAnchorField.into()),
             }
@@ -485,7 +560,7 @@ impl<'names> GlifParser<'names> {
                 b"name" => name = Some(Name::new(&value).map_err(|_| ErrorKind::InvalidName)?),
                 b"color" => color = Some(value.parse().map_err(|_| ErrorKind::BadColor)?),
                 b"identifier" => {
-                    identifier = Some(self.parse_identifier(&value)?);
+                    identifier = self.parse_identifier(&value)?;
                 }
                 _other => return Err(ErrorKind::UnexpectedGuidelineField.into()),
             }
@@ -537,6 +612,14 @@ impl<'names> GlifParser<'names> {
     }
 }
 
+/// Returns the error for an element in `<outline>` that isn't a contour or component.
+fn unexpected_outline_element(name: &[u8], reader: &Reader<&[u8]>) -> GlifLoadError {
+    GlifLoadError::UnexpectedOutlineElement {
+        element: String::from_utf8_lossy(name).into_owned(),
+        position: reader.buffer_position(),
+    }
+}
+
 /// Start parsing XML, expecting an opening `<glyph>` tag.
 ///
 /// On success, returns the glyphs name and the format version.
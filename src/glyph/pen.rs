@@ -0,0 +1,292 @@
+//! Drawing glyph outlines into segment pens, and building them with point pens.
+
+use std::collections::HashSet;
+
+use super::builder::OutlineBuilder;
+use super::{AffineTransform, Component, Contour, Glyph, PointType};
+use crate::error::{DecomposeError, DrawError, ErrorKind};
+use crate::{Identifier, Layer, Name};
+
+/// A segment-oriented pen, modelled on the [fontTools pen protocol].
+///
+/// Implement this trait to receive a glyph's outline from [`Glyph::draw`],
+/// without depending on norad's point representation. Each contour is drawn as
+/// a `move_to`, followed by a number of segments, followed by either
+/// `close_path` (for closed contours) or `end_path` (for open contours).
+///
+/// [fontTools pen protocol]: https://fonttools.readthedocs.io/en/latest/pens/basePen.html
+pub trait Pen {
+    /// Begins a new contour at `pt`.
+    fn move_to(&mut self, pt: (f64, f64));
+
+    /// Draws a straight line to `pt`.
+    fn line_to(&mut self, pt: (f64, f64));
+
+    /// Draws a cubic bézier curve with the control points `c1` and `c2`, ending at `pt`.
+    fn curve_to(&mut self, c1: (f64, f64), c2: (f64, f64), pt: (f64, f64));
+
+    /// Draws a run of quadratic curves, using the TrueType "implied on-curve
+    /// points" principle.
+    ///
+    /// All but the last of the `points` are off-curve points; the last point is
+    /// the on-curve point at which the run ends. There is always at least one
+    /// off-curve point.
+    fn qcurve_to(&mut self, points: &[(f64, f64)]);
+
+    /// Closes the current contour.
+    ///
+    /// If the last segment didn't end at the point passed to `move_to`, this
+    /// implies a straight line back to it.
+    fn close_path(&mut self);
+
+    /// Ends the current contour without closing it.
+    fn end_path(&mut self);
+
+    /// Adds a reference to the glyph `base`, transformed by `transform`.
+    fn add_component(&mut self, base: &Name, transform: AffineTransform);
+}
+
+/// A point-oriented pen, modelled on the [fontTools point pen protocol].
+///
+/// Where a [`Pen`] receives segments, a point pen receives the points of each
+/// contour exactly as they are stored in a glif file. Each contour is drawn as
+/// a `begin_path`, followed by a number of `add_point`s, followed by `end_path`.
+///
+/// [`Glyph::point_pen`] returns a point pen that builds a glyph's outline.
+///
+/// [fontTools point pen protocol]: https://fonttools.readthedocs.io/en/latest/pens/basePen.html
+pub trait PointPen {
+    /// The error returned when the pen is used incorrectly.
+    type Error;
+
+    /// Begins a new contour.
+    fn begin_path(&mut self, identifier: Option<Identifier>) -> Result<(), Self::Error>;
+
+    /// Adds a point to the current contour.
+    ///
+    /// Off-curve points have the type [`PointType::OffCurve`], which
+    /// corresponds to a `segmentType` of `None` in fontTools.
+    fn add_point(
+        &mut self,
+        pt: (f64, f64),
+        typ: PointType,
+        smooth: bool,
+        name: Option<Name>,
+        identifier: Option<Identifier>,
+    ) -> Result<(), Self::Error>;
+
+    /// Ends the current contour.
+    fn end_path(&mut self) -> Result<(), Self::Error>;
+
+    /// Adds a reference to the glyph `base`, transformed by `transform`.
+    fn add_component(
+        &mut self,
+        base: Name,
+        transform: AffineTransform,
+        identifier: Option<Identifier>,
+    ) -> Result<(), Self::Error>;
+}
+
+/// A [`PointPen`] that appends contours and components to a glyph.
+///
+/// Created with [`Glyph::point_pen`]. The point sequence of each contour is
+/// validated as it is drawn, following the rules of the glif specification;
+/// a contour is only added to the glyph once its `end_path` succeeds. A
+/// contour that has not been ended when the pen is dropped is discarded.
+///
+/// The pen does not check that identifiers are unique within the glyph.
+///
+/// # Examples
+///
+/// ```
+/// use norad::{Glyph, PointPen, PointType};
+///
+/// let mut glyph = Glyph::new("square");
+/// let mut pen = glyph.point_pen();
+/// pen.begin_path(None).unwrap();
+/// for pt in [(0.0, 0.0), (0.0, 100.0), (100.0, 100.0), (100.0, 0.0)] {
+///     pen.add_point(pt, PointType::Line, false, None, None).unwrap();
+/// }
+/// pen.end_path().unwrap();
+/// assert_eq!(glyph.contours.len(), 1);
+/// ```
+#[derive(Debug)]
+pub struct GlyphPointPen<'a> {
+    glyph: &'a mut Glyph,
+    builder: OutlineBuilder,
+}
+
+impl PointPen for GlyphPointPen<'_> {
+    type Error = ErrorKind;
+
+    /// Begins a new contour.
+    ///
+    /// Returns an error if the previous contour has not been ended.
+    fn begin_path(&mut self, identifier: Option<Identifier>) -> Result<(), ErrorKind> {
+        self.builder.begin_path(identifier)?;
+        Ok(())
+    }
+
+    /// Adds a point to the current contour.
+    ///
+    /// Returns an error, without adding the point, if no contour has been
+    /// begun, if an off-curve point is smooth, or if the point can't follow
+    /// the preceding points.
+    fn add_point(
+        &mut self,
+        pt: (f64, f64),
+        typ: PointType,
+        smooth: bool,
+        name: Option<Name>,
+        identifier: Option<Identifier>,
+    ) -> Result<(), ErrorKind> {
+        self.builder.add_point(pt, typ, smooth, name, identifier)?;
+        Ok(())
+    }
+
+    /// Ends the current contour and adds it to the glyph, unless it is empty.
+    ///
+    /// Returns an error, discarding the contour, if no contour has been begun
+    /// or if the contour's point sequence is invalid as a whole.
+    fn end_path(&mut self) -> Result<(), ErrorKind> {
+        self.builder.end_path()?;
+        let (contours, _) = std::mem::take(&mut self.builder).finish()?;
+        self.glyph.contours.extend(contours);
+        Ok(())
+    }
+
+    /// Adds a component to the glyph.
+    fn add_component(
+        &mut self,
+        base: Name,
+        transform: AffineTransform,
+        identifier: Option<Identifier>,
+    ) -> Result<(), ErrorKind> {
+        self.glyph.components.push(Component::new(base, transform, identifier, None));
+        Ok(())
+    }
+}
+
+impl Glyph {
+    /// Returns a [`PointPen`] that adds the contours and components drawn with
+    /// it to this glyph.
+    ///
+    /// Existing contours and components are left in place.
+    pub fn point_pen(&mut self) -> GlyphPointPen<'_> {
+        GlyphPointPen { glyph: self, builder: OutlineBuilder::new() }
+    }
+
+    /// Draws the glyph's outline into `pen`.
+    ///
+    /// Contours are drawn in order, followed by the components. If `layer` is
+    /// `None`, components are passed to [`Pen::add_component`]; otherwise they
+    /// are decomposed, drawing the outlines of their base glyphs (looked up in
+    /// `layer`, recursively) transformed by the component transform instead.
+    ///
+    /// A closed contour is drawn starting from its first on-curve point, and
+    /// a closing straight line is left implicit in [`Pen::close_path`].
+    ///
+    /// Returns an error if a contour has an invalid sequence of point types,
+    /// or, when decomposing, if a base glyph is missing or components reference
+    /// each other in a cycle. The pen may have been partially drawn into when
+    /// this happens.
+    pub fn draw(&self, pen: &mut impl Pen, layer: Option<&Layer>) -> Result<(), DrawError> {
+        let mut visiting = HashSet::from([self.name.clone()]);
+        draw_glyph(self, AffineTransform::identity(), pen, layer, &mut visiting)
+    }
+}
+
+fn draw_glyph(
+    glyph: &Glyph,
+    transform: AffineTransform,
+    pen: &mut impl Pen,
+    layer: Option<&Layer>,
+    visiting: &mut HashSet<Name>,
+) -> Result<(), DrawError> {
+    for contour in &glyph.contours {
+        draw_contour(contour, transform, pen)
+            .ok_or_else(|| DrawError::BadContour(glyph.name.clone()))?;
+    }
+    for Component { base, transform: component_transform, .. } in &glyph.components {
+        let transform = transform.concat(*component_transform);
+        let Some(layer) = layer else {
+            pen.add_component(base, transform);
+            continue;
+        };
+        let base_glyph =
+            layer.get_glyph(base).ok_or_else(|| DecomposeError::MissingBase(base.clone()))?;
+        if !visiting.insert(base.clone()) {
+            return Err(DecomposeError::Cycle(base.clone()).into());
+        }
+        draw_glyph(base_glyph, transform, pen, Some(layer), visiting)?;
+        visiting.remove(base);
+    }
+    Ok(())
+}
+
+/// Draws a single contour, returning `None` if its point types are invalid.
+pub(super) fn draw_contour(
+    contour: &Contour,
+    transform: AffineTransform,
+    pen: &mut impl Pen,
+) -> Option<()> {
+    let points = &contour.points;
+    if points.is_empty() {
+        return Some(());
+    }
+    let pt = |i: usize| transform.apply((points[i].x, points[i].y));
+
+    let closed = contour.is_closed();
+    let Some(start) = points.iter().position(|p| p.typ != PointType::OffCurve) else {
+        // a closed quadratic contour made only of off-curve points, which
+        // starts at the implied on-curve point between the last and the first
+        let (last, first) = (pt(points.len() - 1), pt(0));
+        let implied = ((last.0 + first.0) / 2.0, (last.1 + first.1) / 2.0);
+        let mut run: Vec<_> = (0..points.len()).map(pt).collect();
+        run.push(implied);
+        pen.move_to(implied);
+        pen.qcurve_to(&run);
+        pen.close_path();
+        return Some(());
+    };
+    if !closed && start != 0 {
+        return None;
+    }
+
+    pen.move_to(pt(start));
+    let mut offs = Vec::new();
+    // a closed contour ends with the segment leading back to its start
+    let end = if closed { points.len() } else { points.len() - 1 };
+    for i in (start + 1..=start + end).map(|i| i % points.len()) {
+        let point = &points[i];
+        let is_last = closed && i == start;
+        match (&point.typ, offs.as_slice()) {
+            (PointType::OffCurve, _) => {
+                offs.push(pt(i));
+                continue;
+            }
+            (PointType::Move, _) => return None,
+            (PointType::Line | PointType::Curve | PointType::QCurve, []) if is_last => (),
+            (PointType::Line | PointType::Curve | PointType::QCurve, []) => pen.line_to(pt(i)),
+            (PointType::Line, _) => return None,
+            (PointType::Curve, [c1]) => pen.qcurve_to(&[*c1, pt(i)]),
+            (PointType::Curve, [c1, c2]) => pen.curve_to(*c1, *c2, pt(i)),
+            (PointType::Curve, _) => return None,
+            (PointType::QCurve, _) => {
+                offs.push(pt(i));
+                pen.qcurve_to(&offs);
+            }
+        }
+        offs.clear();
+    }
+    if !offs.is_empty() {
+        return None;
+    }
+
+    if closed {
+        pen.close_path();
+    } else {
+        pen.end_path();
+    }
+    Some(())
+}
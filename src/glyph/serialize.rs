@@ -79,7 +79,10 @@ impl Glyph {
             writer.write_event(image.to_event()).map_err(GlifWriteError::Xml)?;
         }
 
-        if !self.contours.is_empty() || !self.components.is_empty() {
+        if !self.contours.is_empty()
+            || !self.components.is_empty()
+            || !self.unknown_outline_elements.is_empty()
+        {
             writer
                 .write_event(Event::Start(BytesStart::new("outline")))
                 .map_err(GlifWriteError::Xml)?;
@@ -89,6 +92,9 @@ impl Glyph {
             for component in &self.components {
                 writer.write_event(component.to_event()).map_err(GlifWriteError::Xml)?;
             }
+            for event in &self.unknown_outline_elements {
+                writer.write_event(event).map_err(GlifWriteError::Xml)?;
+            }
             writer
                 .write_event(Event::End(BytesEnd::new("outline")))
                 .map_err(GlifWriteError::Xml)?;
@@ -110,7 +116,7 @@ impl Glyph {
         // existing lib and insert them there for serialization, otherwise avoid
         // cloning and write out the original.
         let mut lib = self.lib.clone();
-        let object_libs = self.dump_object_libs();
+        let object_libs = self.dump_object_libs()?;
         if !object_libs.is_empty() {
             lib.insert(PUBLIC_OBJECT_LIBS_KEY.into(), object_libs.into());
         }
@@ -148,7 +154,8 @@ fn write_lib_section<T: Write>(
     writer: &mut Writer<T>,
     options: &WriteOptions,
 ) -> Result<(), GlifWriteError> {
-    let as_value: plist::Value = lib.into();
+    let mut as_value: plist::Value = lib.into();
+    options.round_reals(&mut as_value);
     let mut out_buffer = Vec::with_capacity(256); // a reasonable min size?
     as_value
         .to_writer_xml_with_options(&mut out_buffer, options.xml_options())
@@ -175,7 +182,7 @@ fn write_lib_section<T: Write>(
 }
 
 impl Guideline {
-    fn to_event(&self) -> Event {
+    fn to_event(&self) -> Event<'_> {
         let mut start = BytesStart::new("guideline");
         let (x, y, angle) = match self.line {
             Line::Vertical(x) => (Some(x), None, None),
@@ -211,7 +218,7 @@ impl Guideline {
 }
 
 impl Anchor {
-    fn to_event(&self) -> Event {
+    fn to_event(&self) -> Event<'_> {
         let mut start = BytesStart::new("anchor");
 
         if let Some(name) = &self.name {
@@ -234,7 +241,7 @@ impl Anchor {
 }
 
 impl Component {
-    fn to_event(&self) -> Event {
+    fn to_event(&self) -> Event<'_> {
         let mut start = BytesStart::new("component");
         start.push_attribute(("base", &*self.base));
 
@@ -266,7 +273,7 @@ impl Contour {
 }
 
 impl ContourPoint {
-    fn to_event(&self) -> Event {
+    fn to_event(&self) -> Event<'_> {
         let mut start = BytesStart::new("point");
 
         if let Some(name) = &self.name {
@@ -332,7 +339,7 @@ impl Color {
 }
 
 impl Image {
-    fn to_event(&self) -> Event {
+    fn to_event(&self) -> Event<'_> {
         let mut start = BytesStart::new("image");
         start.push_attribute(("fileName", self.file_name.to_str().expect("missing path")));
 
@@ -353,7 +360,7 @@ fn char_to_event(c: char) -> Event<'static> {
 }
 
 fn write_transform_attributes(element: &mut BytesStart, transform: &AffineTransform) {
-    if (transform.x_scale - 1.0).abs() > std::f64::EPSILON {
+    if (transform.x_scale - 1.0).abs() > f64::EPSILON {
         element.push_attribute(("xScale", transform.x_scale.to_string().as_str()));
     }
 
@@ -365,7 +372,7 @@ fn write_transform_attributes(element: &mut BytesStart, transform: &AffineTransf
         element.push_attribute(("yxScale", transform.yx_scale.to_string().as_str()));
     }
 
-    if (transform.y_scale - 1.0).abs() > std::f64::EPSILON {
+    if (transform.y_scale - 1.0).abs() > f64::EPSILON {
         element.push_attribute(("yScale", transform.y_scale.to_string().as_str()));
     }
 
@@ -1,5 +1,9 @@
 use super::parse::parse_glyph;
 use super::*;
+use crate::error::{
+    DecomposeError, DrawError, GlifLoadWarning, GlifWriteError, GlyphValidationError,
+    InterpolatableCoordsError, NamingError, OpenContourError,
+};
 use crate::write::QuoteChar;
 use std::path::PathBuf;
 use std::str::FromStr;
@@ -389,6 +393,142 @@ fn if_no_one_uses_your_lib_is_it_broken() {
     assert!(glyph.lib.get("public.objectLibs").is_none());
 }
 
+#[test]
+fn guideline_libs_round_trip() {
+    let bytes = include_bytes!("../../testdata/guideline_lib.glif");
+    let glyph = parse_glyph(bytes).unwrap();
+    let center = &glyph.guidelines[0];
+    assert_eq!(center.identifier().unwrap().as_str(), "guide-center");
+    let lib = center.lib().unwrap();
+    assert_eq!(lib.get("com.example.customName").unwrap().as_string(), Some("Stem centre"));
+    assert_eq!(lib.get("com.example.locked").unwrap().as_boolean(), Some(true));
+    assert!(glyph.guidelines[2].lib().is_none());
+    // lib data keyed by identifier outside of public.objectLibs is left alone
+    assert!(glyph.lib.contains_key("com.example.guidelineState"));
+
+    let written = glyph.encode_xml().unwrap();
+    assert_eq!(std::str::from_utf8(&written).unwrap(), std::str::from_utf8(bytes).unwrap());
+    assert_eq!(parse_glyph(&written).unwrap(), glyph);
+
+    // libs travel with their guideline when guidelines are reordered or removed
+    let mut glyph = glyph;
+    glyph.guidelines.swap(0, 1);
+    glyph.guidelines.remove(1);
+    let reparsed = parse_glyph(&glyph.encode_xml().unwrap()).unwrap();
+    assert_eq!(reparsed.guidelines.len(), 2);
+    assert_eq!(reparsed.guidelines[0].identifier().unwrap().as_str(), "guide-xheight");
+    assert_eq!(reparsed.guidelines[0].lib(), glyph.guidelines[0].lib());
+}
+
+#[test]
+fn duplicate_object_lib_identifiers() {
+    let bytes = include_bytes!("../../testdata/guideline_lib.glif");
+    let mut glyph = parse_glyph(bytes).unwrap();
+    let copy = glyph.guidelines[0].clone();
+    glyph.guidelines.push(copy);
+    let err = glyph.encode_xml().unwrap_err();
+    assert!(
+        matches!(&err, GlifWriteError::DuplicateObjectLibIdentifier(id) if id.as_str() == "guide-center"),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn object_lib_access() {
+    let mut glyph = decompose_test_layer().get_glyph("a").unwrap().clone();
+    glyph.contours[0].replace_identifier(Identifier::new_raw("outer"));
+    assert!(glyph.object_lib("outer").is_none());
+
+    let mut lib = Plist::new();
+    lib.insert("com.example.locked".into(), true.into());
+    assert_eq!(glyph.replace_object_lib("outer", lib.clone()).unwrap(), None);
+    assert!(matches!(
+        glyph.replace_object_lib("nope", Plist::new()),
+        Err(NamingError::Missing(id)) if id == "nope"
+    ));
+    glyph.object_lib_mut("outer").unwrap().insert("com.example.layer".into(), 2.into());
+
+    let written = glyph.encode_xml().unwrap();
+    assert!(std::str::from_utf8(&written).unwrap().contains("public.objectLibs"));
+    let mut reparsed = parse_glyph(&written).unwrap();
+    let lib = reparsed.object_lib("outer").unwrap();
+    assert_eq!(lib.get("com.example.locked").unwrap().as_boolean(), Some(true));
+    assert_eq!(lib.get("com.example.layer").unwrap().as_signed_integer(), Some(2));
+    assert!(!reparsed.lib.contains_key(PUBLIC_OBJECT_LIBS_KEY));
+
+    // removing the contour removes its lib
+    reparsed.contours.clear();
+    assert!(reparsed.take_object_lib("outer").is_none());
+    let written = reparsed.encode_xml().unwrap();
+    assert!(!std::str::from_utf8(&written).unwrap().contains("public.objectLibs"));
+}
+
+#[test]
+fn interpolatable_coords_round_trip() {
+    let layer = decompose_test_layer();
+    let mut light = layer.get_glyph("a").unwrap().clone();
+    light.width = 100.0;
+    light.components = layer.get_glyph("b").unwrap().components.clone();
+    light.anchors = vec![Anchor::new(50.0, 0.0, Some(Name::new_raw("bottom")), None, None, None)];
+
+    let coords = light.interpolatable_coords();
+    assert_eq!(coords[..4], [100.0, 0.0, 0.0, 0.0]);
+    assert_eq!(coords.len(), 2 + 3 * 2 + 6 + 2);
+    assert_eq!(Glyph::from_interpolatable_coords(&light, &coords).unwrap(), light);
+
+    let mut bold = light.clone();
+    bold.width = 200.0;
+    bold.contours[0].points[1].x = 200.0;
+    bold.components[0].transform.x_offset = 30.0;
+    bold.anchors[0].x = 100.0;
+    assert_eq!(bold.interpolation_schema_hash(), light.interpolation_schema_hash());
+
+    let blended: Vec<_> = light
+        .interpolatable_coords()
+        .iter()
+        .zip(bold.interpolatable_coords())
+        .map(|(l, b)| (l + b) / 2.0)
+        .collect();
+    let medium = Glyph::from_interpolatable_coords(&light, &blended).unwrap();
+    assert_eq!(medium.name(), light.name());
+    assert_close(medium.width, 150.0);
+    assert_close(medium.contours[0].points[1].x, 150.0);
+    assert_close(medium.components[0].transform.x_offset, 20.0);
+    assert_close(medium.components[0].transform.x_scale, 2.0);
+    assert_close(medium.anchors[0].x, 75.0);
+
+    assert!(matches!(
+        Glyph::from_interpolatable_coords(&light, &blended[1..]),
+        Err(InterpolatableCoordsError::WrongLength { expected: 16, found: 15 })
+    ));
+}
+
+#[test]
+fn interpolation_schema_hash() {
+    let layer = decompose_test_layer();
+    let glyph = layer.get_glyph("a").unwrap().clone();
+    let hash = glyph.interpolation_schema_hash();
+    // the hash is stable, so callers may store it; changing it is a breaking change
+    assert_eq!(hash, 17610992924053216585);
+    assert_eq!(layer.get_glyph("b").unwrap().interpolation_schema_hash(), 9392738554411216465);
+
+    let mut moved = glyph.clone();
+    moved.width = 500.0;
+    moved.contours[0].points[0].y = -20.0;
+    assert_eq!(moved.interpolation_schema_hash(), hash);
+
+    let mut retyped = glyph.clone();
+    retyped.contours[0].points[1].typ = PointType::OffCurve;
+    assert_ne!(retyped.interpolation_schema_hash(), hash);
+
+    let mut anchored = glyph.clone();
+    anchored.anchors.push(Anchor::new(0.0, 0.0, Some(Name::new_raw("top")), None, None, None));
+    assert_ne!(anchored.interpolation_schema_hash(), hash);
+    let mut renamed = anchored.clone();
+    renamed.anchors[0].name = Some(Name::new_raw("bottom"));
+    assert_ne!(renamed.interpolation_schema_hash(), anchored.interpolation_schema_hash());
+}
+
 #[test]
 fn parse_note() {
     let bytes = include_bytes!("../../testdata/note.glif");
@@ -742,6 +882,127 @@ fn empty_contours() {
     assert_eq!(test2.contours, vec![]);
 }
 
+#[test]
+fn invalid_identifiers() {
+    for identifier in ["", "ä", "\t"] {
+        let data = format!(
+            r#"
+            <?xml version="1.0" encoding="UTF-8"?>
+            <glyph name="test" format="2">
+                <anchor x="0" y="0" identifier="{identifier}"/>
+            </glyph>
+            "#
+        );
+        let err = parse_glyph(data.as_bytes()).unwrap_err();
+        assert!(matches!(err, GlifLoadError::Parse(ErrorKind::BadIdentifier)), "{err:?}");
+    }
+}
+
+#[test]
+fn invalid_identifiers_lenient() {
+    let data = r#"
+        <?xml version="1.0" encoding="UTF-8"?>
+        <glyph name="test" format="2">
+            <anchor x="1" y="2" name="top" identifier=""/>
+            <guideline x="10" y="0" angle="90" identifier=""/>
+            <outline>
+                <contour identifier="">
+                    <point x="0" y="0" type="line" identifier=""/>
+                    <point x="5" y="5" type="line" identifier="kept"/>
+                </contour>
+                <component base="a" identifier=""/>
+            </outline>
+        </glyph>
+        "#;
+    let (glyph, warnings) =
+        super::parse::GlifParser::from_xml_with_warnings(data.as_bytes(), None, true).unwrap();
+    assert_eq!(glyph.anchors.len(), 1);
+    assert_eq!(glyph.anchors[0].name.as_ref().map(Name::as_str), Some("top"));
+    assert_eq!(glyph.anchors[0].identifier(), None);
+    assert_eq!(glyph.guidelines.len(), 1);
+    assert_eq!(glyph.guidelines[0].identifier(), None);
+    assert_eq!(glyph.contours.len(), 1);
+    assert_eq!(glyph.contours[0].identifier(), None);
+    assert_eq!(glyph.contours[0].points.len(), 2);
+    assert_eq!(glyph.contours[0].points[0].identifier(), None);
+    assert_eq!(glyph.contours[0].points[1].identifier().map(Identifier::as_str), Some("kept"));
+    assert_eq!(glyph.components.len(), 1);
+    assert_eq!(glyph.components[0].identifier(), None);
+    assert_eq!(warnings, vec![GlifLoadWarning::InvalidIdentifier(String::new()); 5]);
+}
+
+#[test]
+fn unknown_outline_element_strict() {
+    let bytes = include_bytes!("../../testdata/bogus_outline_element.glif");
+    let err = parse_glyph(bytes).unwrap_err();
+    let GlifLoadError::UnexpectedOutlineElement { element, position } = err else {
+        panic!("unexpected error: {err:?}");
+    };
+    assert_eq!(element, "foo");
+    let expected_end = std::str::from_utf8(bytes).unwrap().find("<foo/>").unwrap() + 6;
+    assert_eq!(position, expected_end);
+}
+
+#[test]
+fn unknown_outline_element_lenient() {
+    let bytes = include_bytes!("../../testdata/bogus_outline_element.glif");
+    let glyph = super::parse::GlifParser::from_xml(bytes, None, true).unwrap();
+    assert_eq!(glyph.contours.len(), 1);
+    assert_eq!(glyph.contours[0].points.len(), 3);
+    assert_eq!(glyph.components.len(), 1);
+    assert_eq!(&*glyph.components[0].base, "a");
+}
+
+#[test]
+fn unknown_outline_element_lenient_roundtrip() {
+    let bytes = include_bytes!("../../testdata/bogus_outline_element.glif");
+    let glyph = super::parse::GlifParser::from_xml(bytes, None, true).unwrap();
+    let xml = glyph.encode_xml().unwrap();
+    let xml = std::str::from_utf8(&xml).unwrap();
+    assert!(xml.contains("<foo/>"), "{xml}");
+    assert!(xml.contains("<bar size=\"1\">"), "{xml}");
+
+    let reparsed = super::parse::GlifParser::from_xml(xml.as_bytes(), None, true).unwrap();
+    assert_eq!(reparsed, glyph);
+    assert_eq!(reparsed.encode_xml().unwrap(), xml.as_bytes());
+}
+
+#[test]
+fn non_finite_advance_strict() {
+    for bytes in [
+        &include_bytes!("../../testdata/nan_advance.glif")[..],
+        &include_bytes!("../../testdata/inf_advance.glif")[..],
+    ] {
+        let err = parse_glyph(bytes).unwrap_err();
+        assert!(matches!(err, GlifLoadError::Parse(ErrorKind::BadNumber)), "{err:?}");
+    }
+}
+
+#[test]
+fn non_finite_advance_lenient() {
+    let bytes = include_bytes!("../../testdata/nan_advance.glif");
+    let (glyph, warnings) =
+        super::parse::GlifParser::from_xml_with_warnings(bytes, None, true).unwrap();
+    assert_eq!((glyph.width, glyph.height), (0., 500.));
+    assert_eq!(glyph.codepoints, Codepoints::new(['a']));
+    assert!(
+        matches!(
+            warnings.as_slice(),
+            [GlifLoadWarning::NonFiniteAdvance { attribute: "width", value }] if value.is_nan()
+        ),
+        "{warnings:?}"
+    );
+
+    let bytes = include_bytes!("../../testdata/inf_advance.glif");
+    let (glyph, warnings) =
+        super::parse::GlifParser::from_xml_with_warnings(bytes, None, true).unwrap();
+    assert_eq!((glyph.width, glyph.height), (500., 0.));
+    assert_eq!(
+        warnings,
+        [GlifLoadWarning::NonFiniteAdvance { attribute: "height", value: f64::INFINITY }]
+    );
+}
+
 #[test]
 fn pointtype_display_trait() {
     assert_eq!(format!("{}", PointType::Move), "move");
@@ -888,3 +1149,914 @@ fn deduplicate_unicodes2() {
 "#;
     assert_eq!(data2, data2_expected);
 }
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn interpolate_metrics_only() {
+    let bytes = include_bytes!("../../testdata/MutatorSansLightWide.ufo/glyphs/D_.glif");
+    let default = parse_glyph(bytes).unwrap();
+    let mut wide = default.clone();
+    wide.width += 100.0;
+    wide.height += 10.0;
+    let mut narrow = default.clone();
+    narrow.width -= 40.0;
+    narrow.contours.clear();
+
+    let result = default.interpolate_metrics_only(&[&wide, &narrow], &[0.5, 0.25]);
+    assert_eq!(result.width, default.width + 50.0 - 10.0);
+    assert_eq!(result.height, default.height + 5.0);
+    assert_eq!(result.contours, default.contours);
+
+    let result = default.interpolate_metrics_only(&[&wide, &narrow], &[0.0, 0.0]);
+    assert_eq!(result, default);
+}
+
+#[test]
+#[should_panic(expected = "exactly one weight")]
+fn interpolate_metrics_only_mismatched_weights() {
+    let glyph = Glyph::new("a");
+    glyph.interpolate_metrics_only(&[&glyph.clone()], &[]);
+}
+
+fn decompose_test_layer() -> crate::Layer {
+    let mut layer = crate::Layer::default();
+    let mut a = Glyph::new("a");
+    a.contours.push(Contour::new(
+        vec![
+            ContourPoint::new(0., 0., PointType::Line, false, None, None, None),
+            ContourPoint::new(100., 0., PointType::Line, false, None, None, None),
+            ContourPoint::new(100., 100., PointType::Line, false, None, None, None),
+        ],
+        Some(Identifier::new_raw("contour")),
+        None,
+    ));
+    a.anchors.push(Anchor::new(50., 100., Some("top".into()), None, None, None));
+    a.anchors.push(Anchor::new(50., 0., Some("bottom".into()), None, None, None));
+    layer.insert_glyph(a);
+
+    let mut b = Glyph::new("b");
+    let transform =
+        AffineTransform { x_scale: 2., y_scale: 2., x_offset: 10., ..Default::default() };
+    b.components.push(Component::new("a".into(), transform, None, None));
+    b.anchors.push(Anchor::new(0., -10., Some("bottom".into()), None, None, None));
+    layer.insert_glyph(b);
+    layer
+}
+
+#[test]
+fn decompose_with_anchors() {
+    let layer = decompose_test_layer();
+    let mut glyph = layer.get_glyph("b").unwrap().clone();
+    glyph.decompose(&layer, &DecomposeOptions::default().anchors(true)).unwrap();
+
+    assert!(glyph.components.is_empty());
+    assert_eq!(glyph.contours.len(), 1);
+    assert_eq!(glyph.contours[0].identifier(), None);
+    let points: Vec<_> = glyph.contours[0].points.iter().map(|p| (p.x, p.y)).collect();
+    assert_eq!(points, [(10., 0.), (210., 0.), (210., 200.)]);
+
+    // the glyph's own "bottom" anchor wins over the base glyph's
+    let anchors: Vec<_> =
+        glyph.anchors.iter().map(|a| (a.name.as_ref().unwrap().as_str(), a.x, a.y)).collect();
+    assert_eq!(anchors, [("bottom", 0., -10.), ("top", 110., 200.)]);
+}
+
+#[test]
+fn decompose_without_anchors() {
+    let layer = decompose_test_layer();
+    let mut glyph = layer.get_glyph("b").unwrap().clone();
+    glyph.decompose(&layer, &DecomposeOptions::default()).unwrap();
+    assert_eq!(glyph.contours.len(), 1);
+    assert_eq!(glyph.anchors.len(), 1);
+}
+
+#[test]
+fn decompose_nested() {
+    let mut layer = decompose_test_layer();
+    let mut c = Glyph::new("c");
+    let transform = AffineTransform { y_offset: 5., ..Default::default() };
+    c.components.push(Component::new("b".into(), transform, None, None));
+    layer.insert_glyph(c);
+
+    let mut glyph = layer.get_glyph("c").unwrap().clone();
+    glyph.decompose(&layer, &DecomposeOptions::default().anchors(true)).unwrap();
+    let points: Vec<_> = glyph.contours[0].points.iter().map(|p| (p.x, p.y)).collect();
+    assert_eq!(points, [(10., 5.), (210., 5.), (210., 205.)]);
+    let anchors: Vec<_> =
+        glyph.anchors.iter().map(|a| (a.name.as_ref().unwrap().as_str(), a.x, a.y)).collect();
+    assert_eq!(anchors, [("bottom", 0., -5.), ("top", 110., 205.)]);
+}
+
+#[test]
+fn decompose_errors() {
+    let mut layer = decompose_test_layer();
+    let mut glyph = Glyph::new("x");
+    glyph.components.push(Component::new("missing".into(), Default::default(), None, None));
+    let err = glyph.decompose(&layer, &DecomposeOptions::default()).unwrap_err();
+    assert!(matches!(err, crate::error::DecomposeError::MissingBase(name) if &*name == "missing"));
+    assert_eq!(glyph.components.len(), 1);
+
+    let mut a = layer.get_glyph("a").unwrap().clone();
+    a.components.push(Component::new("b".into(), Default::default(), None, None));
+    layer.insert_glyph(a);
+    let mut glyph = layer.get_glyph("b").unwrap().clone();
+    let err = glyph.decompose(&layer, &DecomposeOptions::default()).unwrap_err();
+    assert!(matches!(err, crate::error::DecomposeError::Cycle(name) if &*name == "b"));
+}
+
+fn assert_transforms_close(left: AffineTransform, right: AffineTransform) {
+    let l =
+        [left.x_scale, left.xy_scale, left.yx_scale, left.y_scale, left.x_offset, left.y_offset];
+    let r = [
+        right.x_scale,
+        right.xy_scale,
+        right.yx_scale,
+        right.y_scale,
+        right.x_offset,
+        right.y_offset,
+    ];
+    assert!(l.iter().zip(r).all(|(l, r)| (l - r).abs() < 1e-9), "{left:?} != {right:?}");
+}
+
+fn assert_close(left: f64, right: f64) {
+    assert!((left - right).abs() < 1e-9, "{left} != {right}");
+}
+
+#[test]
+fn decompose_transform_rotation() {
+    let (sin, cos) = 30f64.to_radians().sin_cos();
+    let transform = AffineTransform {
+        x_scale: cos,
+        xy_scale: sin,
+        yx_scale: -sin,
+        y_scale: cos,
+        ..Default::default()
+    };
+    let components = transform.decompose();
+    assert_close(components.rotation, 30.);
+    assert_close(components.scale_x, 1.);
+    assert_close(components.scale_y, 1.);
+    assert_close(components.skew_x, 0.);
+    assert_close(components.skew_y, 0.);
+    assert!(!components.is_reflection());
+    assert_transforms_close(components.into(), transform);
+}
+
+#[test]
+fn decompose_transform_scale() {
+    let transform = AffineTransform { x_scale: 2., y_scale: 3., ..Default::default() };
+    let components = transform.decompose();
+    assert_close(components.rotation, 0.);
+    assert_close(components.scale_x, 2.);
+    assert_close(components.scale_y, 3.);
+    assert_transforms_close(components.into(), transform);
+}
+
+#[test]
+fn decompose_transform_reflection() {
+    let transform = AffineTransform { x_scale: -1., x_offset: 500., ..Default::default() };
+    let components = transform.decompose();
+    assert!(components.is_reflection());
+    assert_close(components.rotation, 0.);
+    assert_close(components.scale_x, -1.);
+    assert_close(components.scale_y, 1.);
+    assert_close(components.translate_x, 500.);
+    assert_transforms_close(components.into(), transform);
+}
+
+#[test]
+fn decompose_transform_combined() {
+    let components = TransformComponents {
+        translate_x: 10.,
+        translate_y: -20.,
+        rotation: 45.,
+        scale_x: 1.5,
+        scale_y: 0.5,
+        skew_x: 10.,
+        skew_y: 0.,
+    };
+    let transform = AffineTransform::from(components);
+    let decomposed = transform.decompose();
+    for (l, r) in [
+        (decomposed.translate_x, 10.),
+        (decomposed.translate_y, -20.),
+        (decomposed.rotation, 45.),
+        (decomposed.scale_x, 1.5),
+        (decomposed.scale_y, 0.5),
+        (decomposed.skew_x, 10.),
+        (decomposed.skew_y, 0.),
+    ] {
+        assert_close(l, r);
+    }
+
+    // arbitrary transforms, including reflections and zero x columns, round-trip
+    for transform in [
+        AffineTransform {
+            x_scale: 0.3,
+            xy_scale: -0.7,
+            yx_scale: 1.2,
+            y_scale: 0.4,
+            x_offset: 5.,
+            y_offset: 6.,
+        },
+        AffineTransform {
+            x_scale: 0.3,
+            xy_scale: 0.7,
+            yx_scale: 1.2,
+            y_scale: -0.4,
+            x_offset: 0.,
+            y_offset: 0.,
+        },
+        AffineTransform {
+            x_scale: 0.,
+            xy_scale: 0.,
+            yx_scale: 1.2,
+            y_scale: -0.4,
+            x_offset: 0.,
+            y_offset: 0.,
+        },
+    ] {
+        assert_transforms_close(transform.decompose().into(), transform);
+    }
+}
+
+/// Asserts that the contour survives a round trip through GLIF, which checks
+/// that its point-type sequence is valid.
+fn assert_contour_valid(contour: &Contour) {
+    let mut glyph = Glyph::new("test");
+    glyph.contours.push(contour.clone());
+    let xml = glyph.encode_xml().unwrap();
+    let parsed = parse_glyph(&xml).unwrap();
+    assert_eq!(parsed.contours[0], *contour);
+}
+
+fn point_types(contour: &Contour) -> Vec<PointType> {
+    contour.points.iter().map(|p| p.typ.clone()).collect()
+}
+
+#[test]
+fn close_contour() {
+    use PointType::*;
+    let mut contour = Contour::new(
+        vec![
+            ContourPoint::new(0., 0., Move, false, None, None, None),
+            ContourPoint::new(0., 100., OffCurve, false, None, None, None),
+            ContourPoint::new(100., 100., OffCurve, false, None, None, None),
+            ContourPoint::new(100., 0., Curve, true, None, None, None),
+        ],
+        None,
+        None,
+    );
+    contour.close();
+    assert!(contour.is_closed());
+    assert_eq!(point_types(&contour), [Line, OffCurve, OffCurve, Curve]);
+    assert_contour_valid(&contour);
+
+    let closed = contour.clone();
+    contour.close();
+    assert_eq!(contour, closed);
+}
+
+#[test]
+fn open_contour_at() {
+    use PointType::*;
+    let mut contour = Contour::new(
+        vec![
+            ContourPoint::new(0., 0., Line, false, None, None, None),
+            ContourPoint::new(0., 100., OffCurve, false, None, None, None),
+            ContourPoint::new(100., 100., OffCurve, false, None, None, None),
+            ContourPoint::new(100., 0., Curve, true, Some(Name::new_raw("corner")), None, None),
+        ],
+        None,
+        None,
+    );
+    let mut open = contour.clone();
+    open.open_at(0).unwrap();
+    assert!(!open.is_closed());
+    assert_eq!(point_types(&open), [Move, OffCurve, OffCurve, Curve, Line]);
+    assert_eq!((open.points[4].x, open.points[4].y), (0., 0.));
+    assert_contour_valid(&open);
+    assert!(matches!(open.open_at(0), Err(OpenContourError::AlreadyOpen)));
+
+    contour.open_at(3).unwrap();
+    assert_eq!(point_types(&contour), [Move, Line, OffCurve, OffCurve, Curve]);
+    assert!(!contour.points[0].smooth);
+    assert_eq!(contour.points[4].name.as_deref(), Some("corner"));
+    assert_eq!((contour.points[4].x, contour.points[4].y), (100., 0.));
+    assert_contour_valid(&contour);
+}
+
+#[test]
+fn open_contour_at_off_curve() {
+    use PointType::*;
+    let mut contour = Contour::new(
+        vec![
+            ContourPoint::new(0., 0., QCurve, false, None, None, None),
+            ContourPoint::new(0., 100., OffCurve, false, None, None, None),
+        ],
+        None,
+        None,
+    );
+    assert!(matches!(contour.open_at(1), Err(OpenContourError::OffCurve(1))));
+    assert_eq!(point_types(&contour), [QCurve, OffCurve]);
+}
+
+#[test]
+fn remove_duplicate_contours() {
+    let bytes = include_bytes!("../../testdata/doubled_contour.glif");
+    let glyph = parse_glyph(bytes).unwrap();
+    assert_eq!(glyph.contours.len(), 3);
+    assert!(glyph.contours[0].is_same_outline(&glyph.contours[2], 0.1));
+    assert!(!glyph.contours[0].is_same_outline(&glyph.contours[2], 0.));
+
+    let mut exact = glyph.clone();
+    assert_eq!(exact.remove_duplicate_contours(0.), 0);
+    assert_eq!(exact.contours.len(), 3);
+
+    let mut deduped = glyph.clone();
+    assert_eq!(deduped.remove_duplicate_contours(0.1), 1);
+    assert_eq!(deduped.contours, glyph.contours[..2]);
+}
+
+#[test]
+fn same_outline_respects_direction_and_openness() {
+    use PointType::*;
+    let point = |x, y, typ| ContourPoint::new(x, y, typ, false, None, None, None);
+    let triangle = Contour::new(
+        vec![point(0., 0., Line), point(100., 0., Line), point(50., 80., Line)],
+        None,
+        None,
+    );
+    let reversed = Contour::new(
+        vec![point(0., 0., Line), point(50., 80., Line), point(100., 0., Line)],
+        None,
+        None,
+    );
+    assert!(!triangle.is_same_outline(&reversed, 0.));
+
+    let mut open = triangle.clone();
+    open.points[0].typ = Move;
+    let mut rotated_open = open.clone();
+    rotated_open.points.rotate_left(1);
+    assert!(open.is_same_outline(&open.clone(), 0.));
+    assert!(!open.is_same_outline(&triangle, 0.));
+    assert!(!open.is_same_outline(&rotated_open, 0.));
+}
+
+/// Twice the signed area of the contour's control polygon, positive for
+/// counter-clockwise contours.
+fn signed_area(contour: &Contour) -> f64 {
+    let points = &contour.points;
+    (0..points.len())
+        .map(|i| {
+            let (a, b) = (&points[i], &points[(i + 1) % points.len()]);
+            a.x * b.y - b.x * a.y
+        })
+        .sum()
+}
+
+#[test]
+fn reverse_contour() {
+    use PointType::*;
+    let point = |x, y, typ| ContourPoint::new(x, y, typ, false, None, None, None);
+    let closed = Contour::new(
+        vec![
+            point(0., 0., Line),
+            point(100., 0., Line),
+            point(100., 50., OffCurve),
+            point(50., 100., OffCurve),
+            point(0., 100., Curve),
+        ],
+        None,
+        None,
+    );
+    let mut reversed = closed.clone();
+    reversed.reverse();
+    assert_eq!(point_types(&reversed), [Line, Line, OffCurve, OffCurve, Curve]);
+    assert_eq!((reversed.points[1].x, reversed.points[1].y), (0., 100.));
+    assert!(signed_area(&reversed) < 0.);
+    assert_contour_valid(&reversed);
+    reversed.reverse();
+    assert_eq!(reversed, closed);
+
+    let mut open = Contour::new(
+        vec![
+            point(0., 0., Move),
+            point(50., 50., OffCurve),
+            point(100., 0., QCurve),
+            point(200., 0., Line),
+        ],
+        None,
+        None,
+    );
+    open.reverse();
+    assert_eq!(point_types(&open), [Move, Line, OffCurve, QCurve]);
+    assert_eq!((open.points[0].x, open.points[3].x), (200., 0.));
+    assert_contour_valid(&open);
+}
+
+#[test]
+fn mirror_horizontal() {
+    let bytes = include_bytes!("../../testdata/doubled_contour.glif");
+    let mut glyph = parse_glyph(bytes).unwrap();
+    glyph.contours.truncate(2);
+    glyph.anchors.push(Anchor::new(100., 700., Some(Name::new_raw("top")), None, None, None));
+    glyph.components.push(Component::new(
+        Name::new_raw("acute"),
+        AffineTransform { x_offset: 100., ..Default::default() },
+        None,
+        None,
+    ));
+    glyph.guidelines.push(Guideline::new(
+        Line::Angle { x: 100., y: 0., degrees: 30. },
+        None,
+        None,
+        None,
+        None,
+    ));
+    let areas: Vec<_> = glyph.contours.iter().map(signed_area).collect();
+
+    let mut mirrored = glyph.clone();
+    mirrored.mirror_horizontal(250.);
+    // directions are the same as before mirroring
+    for (contour, area) in mirrored.contours.iter().zip(&areas) {
+        assert_eq!(signed_area(contour), *area);
+        assert_contour_valid(contour);
+    }
+    let outline = &mirrored.contours[0];
+    assert_eq!((outline.points[0].x, outline.points[0].y), (200., 0.));
+    assert_eq!((mirrored.anchors[0].x, mirrored.anchors[0].y), (400., 700.));
+    let transform = mirrored.components[0].transform;
+    assert_eq!((transform.x_scale, transform.x_offset), (-1., 400.));
+    assert_eq!(mirrored.guidelines[0].line, Line::Angle { x: 400., y: 0., degrees: 150. });
+
+    mirrored.mirror_horizontal(250.);
+    assert_eq!(mirrored, glyph);
+}
+
+#[test]
+fn mirror_vertical() {
+    let bytes = include_bytes!("../../testdata/doubled_contour.glif");
+    let mut glyph = parse_glyph(bytes).unwrap();
+    glyph.contours.truncate(1);
+    glyph.anchors.push(Anchor::new(100., 700., None, None, None, None));
+    let area = signed_area(&glyph.contours[0]);
+
+    glyph.mirror_vertical(350.);
+    assert_eq!(signed_area(&glyph.contours[0]), area);
+    let point = &glyph.contours[0].points[0];
+    assert_eq!((point.x, point.y), (300., 700.));
+    assert_eq!((glyph.anchors[0].x, glyph.anchors[0].y), (100., 0.));
+}
+
+/// A pen that records what it is asked to draw.
+#[derive(Default)]
+struct RecordingPen(Vec<String>);
+
+impl Pen for RecordingPen {
+    fn move_to(&mut self, (x, y): (f64, f64)) {
+        self.0.push(format!("M {x} {y}"));
+    }
+    fn line_to(&mut self, (x, y): (f64, f64)) {
+        self.0.push(format!("L {x} {y}"));
+    }
+    fn curve_to(&mut self, c1: (f64, f64), c2: (f64, f64), (x, y): (f64, f64)) {
+        self.0.push(format!("C {} {} {} {} {x} {y}", c1.0, c1.1, c2.0, c2.1));
+    }
+    fn qcurve_to(&mut self, points: &[(f64, f64)]) {
+        let points: Vec<_> = points.iter().map(|(x, y)| format!("{x} {y}")).collect();
+        self.0.push(format!("Q {}", points.join(" ")));
+    }
+    fn close_path(&mut self) {
+        self.0.push("Z".into());
+    }
+    fn end_path(&mut self) {
+        self.0.push("E".into());
+    }
+    fn add_component(&mut self, base: &Name, t: AffineTransform) {
+        self.0.push(format!("component {base} {} {}", t.x_offset, t.y_offset));
+    }
+}
+
+#[test]
+fn draw_into_pen() {
+    use PointType::*;
+    let point = |x, y, typ| ContourPoint::new(x, y, typ, false, None, None, None);
+    let mut glyph = Glyph::new("test");
+    glyph.contours.push(Contour::new(
+        vec![
+            point(-10., 50., OffCurve),
+            point(0., 0., QCurve),
+            point(100., 0., Line),
+            point(100., 50., OffCurve),
+            point(50., 100., OffCurve),
+            point(0., 100., Curve),
+        ],
+        None,
+        None,
+    ));
+    glyph.contours.push(Contour::new(
+        vec![point(0., 0., Move), point(10., 10., OffCurve), point(20., 0., Curve)],
+        None,
+        None,
+    ));
+    glyph.components.push(Component::new(
+        Name::new_raw("base"),
+        AffineTransform { x_offset: 10., ..Default::default() },
+        None,
+        None,
+    ));
+
+    let mut pen = RecordingPen::default();
+    glyph.draw(&mut pen, None).unwrap();
+    assert_eq!(
+        pen.0,
+        [
+            "M 0 0",
+            "L 100 0",
+            "C 100 50 50 100 0 100",
+            "Q -10 50 0 0",
+            "Z",
+            "M 0 0",
+            "Q 10 10 20 0",
+            "E",
+            "component base 10 0",
+        ]
+    );
+}
+
+#[test]
+fn draw_decomposed() {
+    use crate::Layer;
+    use PointType::*;
+    let point = |x, y, typ| ContourPoint::new(x, y, typ, false, None, None, None);
+    let mut layer = Layer::default();
+    let mut base = Glyph::new("base");
+    base.contours.push(Contour::new(
+        vec![point(0., 0., Line), point(10., 0., Line), point(10., 10., Line)],
+        None,
+        None,
+    ));
+    layer.insert_glyph(base);
+    let mut quad = Glyph::new("quad");
+    quad.contours.push(Contour::new(
+        vec![point(0., 0., OffCurve), point(10., 0., OffCurve), point(10., 10., OffCurve)],
+        None,
+        None,
+    ));
+    layer.insert_glyph(quad);
+    let component = |base: &str, x_offset| {
+        let transform = AffineTransform { x_offset, ..Default::default() };
+        Component::new(Name::new_raw(base), transform, None, None)
+    };
+    let mut nested = Glyph::new("nested");
+    nested.components.push(component("base", 100.));
+    layer.insert_glyph(nested);
+
+    let mut glyph = Glyph::new("test");
+    glyph.components.push(component("nested", 5.));
+    glyph.components.push(component("quad", 0.));
+    let mut pen = RecordingPen::default();
+    glyph.draw(&mut pen, Some(&layer)).unwrap();
+    assert_eq!(
+        pen.0,
+        ["M 105 0", "L 115 0", "L 115 10", "Z", "M 5 5", "Q 0 0 10 0 10 10 5 5", "Z"]
+    );
+
+    glyph.components.push(component("missing", 0.));
+    let err = glyph.draw(&mut RecordingPen::default(), Some(&layer)).unwrap_err();
+    assert!(
+        matches!(err, DrawError::Component(DecomposeError::MissingBase(name)) if name == "missing")
+    );
+    layer.get_glyph_mut("base").unwrap().components.push(component("nested", 0.));
+    let err = glyph.draw(&mut RecordingPen::default(), Some(&layer)).unwrap_err();
+    assert!(matches!(err, DrawError::Component(DecomposeError::Cycle(_))));
+}
+
+#[test]
+fn draw_bad_contour() {
+    use PointType::*;
+    let point = |x, y, typ| ContourPoint::new(x, y, typ, false, None, None, None);
+    let mut glyph = Glyph::new("test");
+    glyph.contours.push(Contour::new(
+        vec![point(0., 0., Move), point(10., 10., OffCurve), point(20., 0., Line)],
+        None,
+        None,
+    ));
+    let err = glyph.draw(&mut RecordingPen::default(), None).unwrap_err();
+    assert!(matches!(err, DrawError::BadContour(name) if name == "test"));
+}
+
+#[test]
+fn point_pen_square() {
+    let mut glyph = Glyph::new("square");
+    let mut pen = glyph.point_pen();
+    pen.begin_path(Some(Identifier::new_raw("square"))).unwrap();
+    for pt in [(0., 0.), (0., 100.), (100., 100.), (100., 0.)] {
+        pen.add_point(pt, PointType::Line, false, None, None).unwrap();
+    }
+    pen.end_path().unwrap();
+    pen.add_component(Name::new_raw("dot"), AffineTransform::default(), None).unwrap();
+
+    assert_eq!(glyph.contours.len(), 1);
+    assert_eq!(glyph.contours[0].identifier(), Some(&Identifier::new_raw("square")));
+    assert!(glyph.contours[0].is_closed());
+    assert_eq!(glyph.components[0].base, "dot");
+
+    let mut pen = RecordingPen::default();
+    glyph.draw(&mut pen, None).unwrap();
+    assert_eq!(pen.0, ["M 0 0", "L 0 100", "L 100 100", "L 100 0", "Z", "component dot 0 0"]);
+}
+
+#[test]
+fn point_pen_validates() {
+    use PointType::*;
+    let mut glyph = Glyph::new("test");
+    let mut pen = glyph.point_pen();
+    assert!(matches!(
+        pen.add_point((0., 0.), Line, false, None, None),
+        Err(ErrorKind::PenPathNotStarted)
+    ));
+    assert!(matches!(pen.end_path(), Err(ErrorKind::PenPathNotStarted)));
+
+    pen.begin_path(None).unwrap();
+    assert!(matches!(pen.begin_path(None), Err(ErrorKind::UnfinishedDrawing)));
+    pen.add_point((0., 0.), Line, false, None, None).unwrap();
+    assert!(matches!(
+        pen.add_point((0., 0.), Move, false, None, None),
+        Err(ErrorKind::UnexpectedMove)
+    ));
+    assert!(matches!(
+        pen.add_point((0., 0.), OffCurve, true, None, None),
+        Err(ErrorKind::UnexpectedSmooth)
+    ));
+    pen.add_point((10., 10.), OffCurve, false, None, None).unwrap();
+    assert!(matches!(
+        pen.add_point((20., 0.), Line, false, None, None),
+        Err(ErrorKind::UnexpectedPointAfterOffCurve)
+    ));
+    pen.add_point((20., 0.), QCurve, false, None, None).unwrap();
+    pen.end_path().unwrap();
+
+    // an open contour must not end with off-curve points
+    pen.begin_path(None).unwrap();
+    pen.add_point((0., 0.), Move, false, None, None).unwrap();
+    pen.add_point((10., 10.), OffCurve, false, None, None).unwrap();
+    assert!(matches!(pen.end_path(), Err(ErrorKind::TrailingOffCurves)));
+
+    // the failed contour has been discarded
+    pen.begin_path(None).unwrap();
+    pen.end_path().unwrap();
+    assert_eq!(glyph.contours.len(), 1);
+    assert_eq!(glyph.contours[0].points.len(), 3);
+}
+
+#[test]
+fn self_intersections() {
+    let bytes = include_bytes!("../../testdata/figure_eight.glif");
+    let glyph = parse_glyph(bytes).unwrap();
+    assert!(glyph.contours[0].self_intersects());
+    assert!(!glyph.contours[1].self_intersects());
+
+    let found = glyph.find_self_intersections();
+    assert_eq!(found.len(), 1, "{found:?}");
+    let (contour, (x, y)) = found[0];
+    assert_eq!(contour, 0);
+    assert!((x - 250.).abs() < 1. && (y - 350.).abs() < 1., "{x}, {y}");
+
+    // smooth curves and shared points between neighbouring segments are fine
+    let bytes = include_bytes!("../../testdata/doubled_contour.glif");
+    let glyph = parse_glyph(bytes).unwrap();
+    assert!(glyph.find_self_intersections().is_empty());
+}
+
+#[test]
+fn self_intersections_bowtie() {
+    use PointType::*;
+    let point = |x, y, typ| ContourPoint::new(x, y, typ, false, None, None, None);
+    let bowtie = Contour::new(
+        vec![
+            point(0., 0., Line),
+            point(100., 100., Line),
+            point(100., 0., Line),
+            point(0., 100., Line),
+        ],
+        None,
+        None,
+    );
+    assert_eq!(bowtie.self_intersections(), [(50., 50.)]);
+
+    // an open contour is not closed by a line back to the start
+    let mut open = Contour::new(
+        vec![
+            point(0., 0., Move),
+            point(100., 0., Line),
+            point(100., 100., Line),
+            point(0., 100., Line),
+        ],
+        None,
+        None,
+    );
+    assert!(!open.self_intersects());
+    open.points.push(point(100., -100., Line));
+    assert_eq!(open.self_intersections(), [(50., 0.)]);
+}
+
+#[test]
+fn contour_bounds() {
+    use PointType::*;
+    let point = |x, y, typ| ContourPoint::new(x, y, typ, false, None, None, None);
+    let arch = Contour::new(
+        vec![
+            point(0., 0., Line),
+            point(0., 100., OffCurve),
+            point(100., 100., OffCurve),
+            point(100., 0., Curve),
+        ],
+        None,
+        None,
+    );
+    // the curve peaks below its control points
+    let bounds = arch.bounds().unwrap();
+    assert_eq!(bounds, Bounds { x_min: 0., y_min: 0., x_max: 100., y_max: 75. });
+
+    let quad = Contour::new(
+        vec![point(0., 0., Move), point(50., -100., OffCurve), point(100., 0., QCurve)],
+        None,
+        None,
+    );
+    assert_eq!(quad.bounds().unwrap().y_min, -50.);
+
+    assert_eq!(Contour::default().bounds(), None);
+    let bad = Contour::new(vec![point(0., 0., Line), point(0., 0., Move)], None, None);
+    assert_eq!(bad.bounds(), None);
+}
+
+#[test]
+fn glyph_bounds_and_sidebearings() {
+    let layer = decompose_test_layer();
+    let mut glyph = layer.get_glyph("b").unwrap().clone();
+    glyph.width = 200.;
+    assert_eq!(glyph.bounds(None).unwrap(), None);
+    assert_eq!(glyph.sidebearings(None).unwrap(), None);
+
+    let bounds = glyph.bounds(Some(&layer)).unwrap().unwrap();
+    assert_eq!(bounds, Bounds { x_min: 10., y_min: 0., x_max: 210., y_max: 200. });
+    assert_eq!(glyph.sidebearings(Some(&layer)).unwrap(), Some((10., -10.)));
+
+    glyph.components[0].base = "missing".into();
+    assert!(glyph.bounds(Some(&layer)).is_err());
+}
+
+#[test]
+fn contour_length_and_centroid() {
+    use PointType::*;
+    let point = |x, y, typ| ContourPoint::new(x, y, typ, false, None, None, None);
+    let square = Contour::new(
+        vec![point(0., 0., Line), point(1., 0., Line), point(1., 1., Line), point(0., 1., Line)],
+        None,
+        None,
+    );
+    assert_eq!(square.length(), Some(4.));
+    let (x, y) = square.centroid().unwrap();
+    assert_close(x, 0.5);
+    assert_close(y, 0.5);
+
+    // a circle of radius 100 around (200, 300), approximated by four cubics
+    let k = 0.552_284_749_8 * 100.;
+    let circle = Contour::new(
+        vec![
+            point(300., 300., Curve),
+            point(300., 300. + k, OffCurve),
+            point(200. + k, 400., OffCurve),
+            point(200., 400., Curve),
+            point(200. - k, 400., OffCurve),
+            point(100., 300. + k, OffCurve),
+            point(100., 300., Curve),
+            point(100., 300. - k, OffCurve),
+            point(200. - k, 200., OffCurve),
+            point(200., 200., Curve),
+            point(200. + k, 200., OffCurve),
+            point(300., 300. - k, OffCurve),
+        ],
+        None,
+        None,
+    );
+    let length = circle.length().unwrap();
+    assert!((length - 628.406_679).abs() < 1e-3, "{length}");
+    assert!((length - 200. * std::f64::consts::PI).abs() < 0.2);
+    let (x, y) = circle.centroid().unwrap();
+    assert_close(x, 200.);
+    assert_close(y, 300.);
+
+    // the centroid doesn't depend on direction, or on the curves being symmetric
+    let mut reversed = circle.clone();
+    reversed.points[1].y += 50.;
+    let skewed = reversed.centroid().unwrap();
+    reversed.reverse();
+    let (x, y) = reversed.centroid().unwrap();
+    assert_close(x, skewed.0);
+    assert_close(y, skewed.1);
+    assert!(skewed.0 > 200. && skewed.1 > 300.);
+
+    let open = Contour::new(
+        vec![point(0., 0., Move), point(3., 0., Line), point(3., 4., Line)],
+        None,
+        None,
+    );
+    assert_eq!(open.length(), Some(7.));
+    assert_eq!(open.centroid(), None);
+
+    let flat = Contour::new(vec![point(0., 0., Line), point(10., 0., Line)], None, None);
+    assert_eq!(flat.length(), Some(20.));
+    assert_eq!(flat.centroid(), None);
+    let bad = Contour::new(vec![point(0., 0., Line), point(0., 0., Move)], None, None);
+    assert_eq!(bad.length(), None);
+}
+
+#[test]
+fn glyph_centroid() {
+    let layer = decompose_test_layer();
+    let glyph = layer.get_glyph("b").unwrap();
+    assert_eq!(glyph.centroid(None).unwrap(), None);
+    // the triangle (10, 0), (210, 0), (210, 200)
+    let (x, y) = glyph.centroid(Some(&layer)).unwrap().unwrap();
+    assert_close(x, 430. / 3.);
+    assert_close(y, 200. / 3.);
+
+    // a square with a counter in one corner, running against the outer contour
+    let point = |x, y| ContourPoint::new(x, y, PointType::Line, false, None, None, None);
+    let mut glyph = Glyph::new("L");
+    for (corners, size) in [
+        ([(0., 0.), (1., 0.), (1., 1.), (0., 1.)], 100.),
+        ([(0., 0.), (0., 1.), (1., 1.), (1., 0.)], 50.),
+    ] {
+        let points = corners.iter().map(|&(x, y)| point(x * size, y * size)).collect();
+        glyph.contours.push(Contour::new(points, None, None));
+    }
+    let (x, y) = glyph.centroid(None).unwrap().unwrap();
+    let expected = (100. * 100. * 50. - 50. * 50. * 25.) / (100. * 100. - 50. * 50.);
+    assert_close(x, expected);
+    assert_close(y, expected);
+}
+
+#[test]
+fn validate_missing_component_base() {
+    let bytes = include_bytes!("../../testdata/missing_component_base.glif");
+    let glyph = parse_glyph(bytes).unwrap();
+    let mut layer = crate::Layer::default();
+    layer.insert_glyph(Glyph::new("A"));
+    layer.insert_glyph(glyph.clone());
+
+    let errors = glyph.validate(&layer);
+    assert_eq!(
+        errors,
+        [GlyphValidationError::MissingComponentBase { index: 1, base: "acutecomb".into() }]
+    );
+    assert_eq!(
+        errors[0].to_string(),
+        "component 1 refers to base glyph 'acutecomb', which does not exist in the layer"
+    );
+
+    layer.insert_glyph(Glyph::new("acutecomb"));
+    assert!(glyph.validate(&layer).is_empty());
+}
+
+#[test]
+fn curve_types_summary() {
+    let bytes = include_bytes!("../../testdata/mixed_curves.glif");
+    let mut glyph = parse_glyph(bytes).unwrap();
+    assert_eq!(glyph.curve_types(), CurveTypeSummary::Mixed);
+
+    let quadratic = glyph.contours.pop().unwrap();
+    assert_eq!(glyph.curve_types(), CurveTypeSummary::Cubic);
+    glyph.contours = vec![quadratic];
+    assert_eq!(glyph.curve_types(), CurveTypeSummary::Quadratic);
+
+    // a curve point with a single off-curve point is still cubic, as in CFF sources
+    use PointType::*;
+    let point = |x, y, typ| ContourPoint::new(x, y, typ, false, None, None, None);
+    glyph.contours = vec![Contour::new(
+        vec![point(0., 0., Line), point(50., 100., OffCurve), point(100., 0., Curve)],
+        None,
+        None,
+    )];
+    assert_eq!(glyph.curve_types(), CurveTypeSummary::Cubic);
+
+    // a closed contour of only off-curve points is quadratic
+    glyph.contours = vec![Contour::new(
+        vec![point(0., 0., OffCurve), point(100., 0., OffCurve), point(50., 100., OffCurve)],
+        None,
+        None,
+    )];
+    assert_eq!(glyph.curve_types(), CurveTypeSummary::Quadratic);
+
+    glyph.contours = vec![Contour::new(
+        vec![point(0., 0., Line), point(100., 0., Line), point(100., 100., Line)],
+        None,
+        None,
+    )];
+    assert_eq!(glyph.curve_types(), CurveTypeSummary::NoCurves);
+    glyph.contours.clear();
+    assert_eq!(glyph.curve_types(), CurveTypeSummary::NoCurves);
+}
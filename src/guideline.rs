@@ -15,9 +15,9 @@ pub struct Guideline {
     pub color: Option<Color>,
     /// Unique identifier for the guideline within the glyph. This attribute is only required
     /// when a lib is present and should otherwise only be added as needed.
-    identifier: Option<Identifier>,
+    pub(crate) identifier: Option<Identifier>,
     /// The guideline's lib for arbitary data.
-    lib: Option<Plist>,
+    pub(crate) lib: Option<Plist>,
 }
 
 /// An infinite line.
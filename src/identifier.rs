@@ -20,7 +20,7 @@ pub struct Identifier(Arc<str>);
 impl Identifier {
     /// Create a new [`Identifier`] from a string, if it is valid.
     ///
-    /// A valid identifier must have between 0 and 100 characters, and each
+    /// A valid identifier must have between 1 and 100 characters, and each
     /// character must be in the printable ASCII range, 0x20 to 0x7E.
     pub fn new(string: &str) -> Result<Self, ErrorKind> {
         if is_valid_identifier(string) {
@@ -49,7 +49,7 @@ impl Identifier {
 }
 
 fn is_valid_identifier(s: &str) -> bool {
-    s.len() <= 100 && s.bytes().all(|b| (0x20..=0x7E).contains(&b))
+    !s.is_empty() && s.len() <= 100 && s.bytes().all(|b| (0x20..=0x7E).contains(&b))
 }
 
 impl AsRef<str> for Identifier {
@@ -72,7 +72,7 @@ impl<'a> PartialEq<&'a str> for Identifier {
     }
 }
 
-impl<'a> PartialEq<Identifier> for &'a str {
+impl PartialEq<Identifier> for &str {
     fn eq(&self, other: &Identifier) -> bool {
         other == self
     }
@@ -127,4 +127,32 @@ mod tests {
         let i3 = Identifier::new("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa");
         assert!(i3.is_err());
     }
+
+    #[test]
+    fn identifier_length_boundaries() {
+        assert!(Identifier::new("").is_err());
+        assert!(Identifier::new("a").is_ok());
+        assert!(Identifier::new(&"a".repeat(100)).is_ok());
+        assert!(Identifier::new(&"a".repeat(101)).is_err());
+    }
+
+    #[test]
+    fn identifier_illegal_characters() {
+        assert!(Identifier::new("\u{1F}").is_err());
+        assert!(Identifier::new("\u{7F}").is_err());
+        assert!(Identifier::new("tab\there").is_err());
+        assert!(Identifier::new("new\nline").is_err());
+        assert!(Identifier::new("é").is_err());
+        assert!(Identifier::new(" ").is_ok());
+        assert!(Identifier::new("~").is_ok());
+    }
+
+    #[test]
+    fn identifier_deserialize_invalid() {
+        use serde_test::{assert_de_tokens_error, Token};
+        assert_de_tokens_error::<Identifier>(
+            &[Token::Str("")],
+            "an identifier must be between 1 and 100 characters long and contain only ASCII characters in the range 0x20 to 0x7E",
+        );
+    }
 }
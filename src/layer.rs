@@ -1,14 +1,16 @@
 use std::collections::{BTreeMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError, Weak};
 
 #[cfg(feature = "rayon")]
 use rayon::prelude::*;
 
 use crate::data_request::LayerFilter;
-use crate::error::{FontLoadError, LayerLoadError, LayerWriteError, NamingError};
+use crate::error::{FontLoadError, LayerLoadError, LayerWriteError, LoadWarning, NamingError};
 use crate::names::NameList;
 use crate::shared_types::Color;
+use crate::spacing::SpacingReport;
 use crate::Name;
 use crate::{util, Glyph, Plist, WriteOptions};
 
@@ -52,11 +54,14 @@ impl LayerContents {
     /// we will assume the pre-UFOv3 behaviour, and expect a single glyphs dir.
     ///
     /// The `glyph_names` argument allows norad to reuse glyph name strings,
-    /// reducing memory use.
+    /// reducing memory use. Problems tolerated in `lenient` mode are
+    /// appended to `warnings`.
     pub(crate) fn load(
         base_dir: &Path,
         glyph_names: &NameList,
         filter: &LayerFilter,
+        lenient: bool,
+        warnings: &mut Vec<LoadWarning>,
     ) -> Result<LayerContents, FontLoadError> {
         let layer_contents_path = base_dir.join(LAYER_CONTENTS_FILE);
         let to_load: Vec<(Name, PathBuf)> = if layer_contents_path.exists() {
@@ -71,13 +76,13 @@ impl LayerContents {
             .filter(|(name, path)| filter.should_load(name, path))
             .map(|(name, path)| {
                 let layer_path = base_dir.join(path);
-                Layer::load_impl(&layer_path, name.clone(), glyph_names).map_err(|source| {
-                    FontLoadError::Layer {
+                Layer::load_impl(&layer_path, name.clone(), glyph_names, lenient, warnings).map_err(
+                    |source| FontLoadError::Layer {
                         name: name.to_string(),
                         path: layer_path,
                         source: Box::new(source),
-                    }
-                })
+                    },
+                )
             })
             .collect::<Result<_, _>>()?;
         // we always need a default layer, so add an empty one if it's filtered
@@ -237,12 +242,42 @@ impl LayerContents {
     where
         F: FnMut(&Layer) -> bool,
     {
-        self.layers.retain(|layer| layer.name == DEFAULT_LAYER_NAME || predicate(layer))
+        // the default layer is always first
+        let mut is_default = true;
+        let path_set = &mut self.path_set;
+        self.layers.retain(|layer| {
+            let keep = std::mem::take(&mut is_default) || predicate(layer);
+            if !keep {
+                path_set.remove(&layer.path.to_string_lossy().to_lowercase());
+            }
+            keep
+        })
     }
 
-    /// Removes any layers that contain no glyphs
-    pub fn remove_empty_layers(&mut self) {
-        self.retain(|l| !l.is_empty());
+    /// Removes any non-default layers that contain no glyphs, returning their names.
+    ///
+    /// The default layer is never removed, even if it is empty.
+    pub fn remove_empty_layers(&mut self) -> Vec<Name> {
+        let mut removed = Vec::new();
+        self.retain(|layer| {
+            if layer.is_empty() {
+                removed.push(layer.name.clone());
+            }
+            !layer.is_empty()
+        });
+        removed
+    }
+}
+
+impl LayerContents {
+    /// Returns a [`LayerContents`] whose only layer is a copy of `layer`,
+    /// promoted to be the default layer.
+    ///
+    /// The copy keeps the layer's name, color, lib and glyph file names, and
+    /// shares its glyphs with `layer`.
+    pub(crate) fn from_layer(layer: &Layer) -> Self {
+        let layer = Layer { path: DEFAULT_GLYPHS_DIRNAME.into(), ..layer.clone() };
+        LayerContents { layers: vec![layer], path_set: HashSet::new() }
     }
 }
 
@@ -257,10 +292,21 @@ impl Default for LayerContents {
 ///
 /// Conceptually, a layer is just a collection of glyphs.
 ///
+/// # Cloning
+///
+/// Glyphs are stored behind reference-counted pointers, so cloning a layer
+/// (or a whole [`Font`](crate::Font)) is cheap: the clone shares its glyphs with
+/// the original. Shared glyphs are copied on write, the first time they are
+/// accessed mutably through either layer (for instance with
+/// [`get_glyph_mut`](Layer::get_glyph_mut), [`iter_mut`](Layer::iter_mut) or
+/// [`retain`](Layer::retain)), so changes made through one layer are never
+/// visible through the other. Note that the methods that iterate mutably copy
+/// every shared glyph they visit, whether or not it is modified.
+///
 /// [UFO layer]: http://unifiedfontobject.org/versions/ufo3/glyphs/
 #[derive(Debug, Clone, PartialEq)]
 pub struct Layer {
-    pub(crate) glyphs: BTreeMap<Name, Glyph>,
+    pub(crate) glyphs: BTreeMap<Name, Arc<Glyph>>,
     pub(crate) name: Name,
     pub(crate) path: PathBuf,
     contents: BTreeMap<Name, PathBuf>,
@@ -275,6 +321,8 @@ pub struct Layer {
     ///
     /// An empty lib is not serialized.
     pub lib: Plist,
+    /// The last spacing report computed for this layer.
+    pub(crate) spacing_cache: SpacingCache,
 }
 
 impl Layer {
@@ -291,6 +339,7 @@ impl Layer {
             path_set: HashSet::new(),
             color: None,
             lib: Default::default(),
+            spacing_cache: Default::default(),
         }
     }
 
@@ -306,17 +355,21 @@ impl Layer {
         let path = path.as_ref();
         let names = NameList::default();
         let name = Name::new_raw(name);
-        Layer::load_impl(path, name, &names)
+        Layer::load_impl(path, name, &names, false, &mut Vec::new())
     }
 
     /// The actual loading logic.
     ///
     /// `names` is a map of glyphnames; we pass it throughout parsing
     /// so that we reuse the same `Arc<str>` for identical names.
+    ///
+    /// Problems tolerated in `lenient` mode are appended to `warnings`.
     pub(crate) fn load_impl(
         path: &Path,
         name: Name,
         names: &NameList,
+        lenient: bool,
+        warnings: &mut Vec<LoadWarning>,
     ) -> Result<Layer, LayerLoadError> {
         let contents_path = path.join(CONTENTS_FILE);
         if !contents_path.exists() {
@@ -333,24 +386,34 @@ impl Layer {
         #[cfg(not(feature = "rayon"))]
         let iter = contents.iter();
 
-        let glyphs = iter
+        let loaded: Vec<_> = iter
             .map(|(name, glyph_path)| {
                 let name = names.get(name);
                 let glyph_path = path.join(glyph_path);
 
-                Glyph::load_with_names(&glyph_path, names)
+                Glyph::load_with_names(&glyph_path, names, lenient)
                     .map_err(|source| LayerLoadError::Glyph {
                         name: name.to_string(),
                         path: glyph_path,
                         source,
                     })
-                    .map(|mut glyph| {
+                    .map(|(mut glyph, glyph_warnings)| {
                         glyph.name = name.clone();
-                        (name, glyph)
+                        (name, Arc::new(glyph), glyph_warnings)
                     })
             })
             .collect::<Result<_, _>>()?;
 
+        let mut glyphs = BTreeMap::new();
+        for (glyph_name, glyph, glyph_warnings) in loaded {
+            warnings.extend(glyph_warnings.into_iter().map(|warning| LoadWarning {
+                layer: name.clone(),
+                glyph: glyph_name.clone(),
+                warning,
+            }));
+            glyphs.insert(glyph_name, glyph);
+        }
+
         let layerinfo_path = path.join(LAYER_INFO_FILE);
         let (color, lib) = if layerinfo_path.exists() {
             Self::parse_layer_info(&layerinfo_path)?
@@ -361,7 +424,16 @@ impl Layer {
         // for us to get this far, the path must have a file name
         let path = path.file_name().unwrap().into();
 
-        Ok(Layer { glyphs, name, path, contents, path_set, color, lib })
+        Ok(Layer {
+            glyphs,
+            name,
+            path,
+            contents,
+            path_set,
+            color,
+            lib,
+            spacing_cache: Default::default(),
+        })
     }
 
     fn parse_layer_info(path: &Path) -> Result<(Option<Color>, Plist), LayerLoadError> {
@@ -419,17 +491,30 @@ impl Layer {
         &self,
         path: &Path,
         opts: &WriteOptions,
+    ) -> Result<(), LayerWriteError> {
+        self.save_with_contents(path, opts, &self.contents)
+    }
+
+    /// Serialize this layer to the given `path`, writing each glyph to the file
+    /// name given by `contents`, which must list every glyph in the layer.
+    ///
+    /// The path should not exist.
+    pub(crate) fn save_with_contents(
+        &self,
+        path: &Path,
+        opts: &WriteOptions,
+        contents: &BTreeMap<Name, PathBuf>,
     ) -> Result<(), LayerWriteError> {
         fs::create_dir(path).map_err(LayerWriteError::CreateDir)?;
-        crate::write::write_xml_to_file(&path.join(CONTENTS_FILE), &self.contents, opts)
+        crate::write::write_xml_to_file(&path.join(CONTENTS_FILE), contents, opts)
             .map_err(LayerWriteError::Contents)?;
 
         self.layerinfo_to_file_if_needed(path, opts)?;
 
         #[cfg(feature = "rayon")]
-        let iter = self.contents.par_iter();
+        let iter = contents.par_iter();
         #[cfg(not(feature = "rayon"))]
-        let mut iter = self.contents.iter();
+        let mut iter = contents.iter();
 
         iter.try_for_each(|(name, glyph_path)| {
             let glyph = self.glyphs.get(name).expect("all glyphs in contents must exist.");
@@ -442,6 +527,63 @@ impl Layer {
         })
     }
 
+    /// Reads the glyph file names from the `contents.plist` file in the layer
+    /// directory `path`, if there is one and it can be parsed.
+    pub(crate) fn read_file_names(path: &Path) -> Option<BTreeMap<Name, PathBuf>> {
+        plist::from_file(path.join(CONTENTS_FILE)).ok()
+    }
+
+    /// Returns the layer's glyph file names, with the glyphs listed in
+    /// `existing` moved to the file name they have there.
+    ///
+    /// Only plain file names are reused, so `existing` can't make glyphs be
+    /// written outside of the layer directory. Glyphs whose current file name
+    /// clashes with a reused one get a new file name.
+    pub(crate) fn contents_reusing(
+        &self,
+        existing: &BTreeMap<Name, PathBuf>,
+    ) -> BTreeMap<Name, PathBuf> {
+        let lowercase = |path: &Path| path.to_string_lossy().to_lowercase();
+        let mut used = HashSet::new();
+        let mut contents = BTreeMap::new();
+        for name in self.contents.keys() {
+            let Some(path) = existing.get(name) else {
+                continue;
+            };
+            let is_file_name = path.file_name() == Some(path.as_os_str());
+            if is_file_name && used.insert(lowercase(path)) {
+                contents.insert(name.clone(), path.clone());
+            }
+        }
+        for (name, path) in &self.contents {
+            if contents.contains_key(name) {
+                continue;
+            }
+            let path = if used.contains(&lowercase(path)) {
+                util::default_file_name_for_glyph_name(name, &used)
+            } else {
+                path.clone()
+            };
+            used.insert(lowercase(&path));
+            contents.insert(name.clone(), path);
+        }
+        contents
+    }
+
+    /// Returns the paths, relative to the layer directory, of the files that
+    /// [`Layer::save_with_options`] would write, or, given the `existing` file
+    /// names to reuse, the files that [`Layer::save_with_contents`] would write
+    /// with the contents from [`Layer::contents_reusing`].
+    pub(crate) fn planned_files(&self, existing: Option<&BTreeMap<Name, PathBuf>>) -> Vec<PathBuf> {
+        let layerinfo =
+            (self.color.is_some() || !self.lib.is_empty()).then(|| PathBuf::from(LAYER_INFO_FILE));
+        let glyph_files = match existing {
+            Some(existing) => self.contents_reusing(existing).into_values().collect(),
+            None => self.contents.values().cloned().collect::<Vec<_>>(),
+        };
+        std::iter::once(PathBuf::from(CONTENTS_FILE)).chain(layerinfo).chain(glyph_files).collect()
+    }
+
     /// Returns the number of [`Glyph`]s in the layer.
     pub fn len(&self) -> usize {
         self.glyphs.len()
@@ -469,18 +611,28 @@ impl Layer {
     }
 
     /// Gets the given key's corresponding entry in the map for in-place manipulation.
-    pub fn entry(&mut self, glyph: Name) -> std::collections::btree_map::Entry<Name, Glyph> {
-        self.glyphs.entry(glyph)
+    ///
+    /// A glyph inserted through the entry is given a file name, as with
+    /// [`Layer::insert_glyph`], and a glyph shared with a clone of this layer is
+    /// only copied when it is accessed mutably.
+    pub fn entry(&mut self, glyph: Name) -> GlyphEntry<'_> {
+        if self.glyphs.contains_key(&glyph) {
+            GlyphEntry::Occupied(OccupiedGlyphEntry { layer: self, name: glyph })
+        } else {
+            GlyphEntry::Vacant(VacantGlyphEntry { layer: self, name: glyph })
+        }
     }
 
     /// Returns a reference to the glyph with the given name, if it exists.
     pub fn get_glyph(&self, glyph: &str) -> Option<&Glyph> {
-        self.glyphs.get(glyph)
+        self.glyphs.get(glyph).map(|glyph| glyph.as_ref())
     }
 
     /// Returns a mutable reference to the glyph with the given name, if it exists.
+    ///
+    /// If the glyph is shared with a clone of this layer, it is copied first.
     pub fn get_glyph_mut(&mut self, glyph: &str) -> Option<&mut Glyph> {
-        self.glyphs.get_mut(glyph)
+        self.glyphs.get_mut(glyph).map(Arc::make_mut)
     }
 
     /// Returns `true` if this layer contains a glyph with this `name`.
@@ -494,12 +646,17 @@ impl Layer {
     /// the glyph's name.
     pub fn insert_glyph(&mut self, glyph: impl Into<Glyph>) {
         let glyph = glyph.into();
-        if !self.contents.contains_key(&glyph.name) {
-            let path = crate::util::default_file_name_for_glyph_name(&glyph.name, &self.path_set);
+        self.add_file_name(&glyph.name);
+        self.glyphs.insert(glyph.name.clone(), Arc::new(glyph));
+    }
+
+    /// Gives the glyph `name` a file name, unless it already has one.
+    fn add_file_name(&mut self, name: &Name) {
+        if !self.contents.contains_key(name) {
+            let path = crate::util::default_file_name_for_glyph_name(name, &self.path_set);
             self.path_set.insert(path.to_string_lossy().to_lowercase());
-            self.contents.insert(glyph.name.clone(), path);
+            self.contents.insert(name.clone(), path);
         }
-        self.glyphs.insert(glyph.name.clone(), glyph);
     }
 
     /// Remove all glyphs in the layer. Leave color and the lib untouched.
@@ -514,11 +671,17 @@ impl Layer {
         if let Some(path) = self.contents.remove(name) {
             self.path_set.remove(&path.to_string_lossy().to_lowercase());
         }
-        self.glyphs.remove(name)
+        self.glyphs
+            .remove(name)
+            .map(|glyph| Arc::try_unwrap(glyph).unwrap_or_else(|g| (*g).clone()))
     }
 
     /// Rename a glyph.
     ///
+    /// The glyph is given a new file name derived from the new name, and every
+    /// component in this layer that uses the glyph as its base is updated to
+    /// refer to the new name. Other layers, groups and kerning are not touched.
+    ///
     /// If `overwrite` is true, and a glyph with the new name exists, it will
     /// be replaced.
     ///
@@ -538,28 +701,53 @@ impl Layer {
         } else {
             let name = Name::new(new).map_err(|_| NamingError::Invalid(new.into()))?;
             let mut g = self.remove_glyph(old).unwrap();
-            g.name = name;
+            g.name = name.clone();
             self.insert_glyph(g);
+            for glyph in self.glyphs.values_mut() {
+                // only copy glyphs shared with a clone if they actually change
+                if glyph.components.iter().any(|c| c.base == old) {
+                    for component in &mut Arc::make_mut(glyph).components {
+                        if component.base == old {
+                            component.base = name.clone();
+                        }
+                    }
+                }
+            }
             Ok(())
         }
     }
 
     /// Returns an iterator over the glyphs in this layer.
     pub fn iter(&self) -> impl Iterator<Item = &Glyph> + '_ {
-        self.glyphs.values()
+        self.glyphs.values().map(|glyph| glyph.as_ref())
     }
 
     /// Returns an iterator over the glyphs in this layer, mutably.
+    ///
+    /// Glyphs shared with a clone of this layer are copied as they are visited,
+    /// so use [`Layer::iter`] when the glyphs only need to be read.
     pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Glyph> {
-        self.glyphs.values_mut()
+        self.glyphs.values_mut().map(Arc::make_mut)
     }
 
     /// Retains only the elements specified by the predicate.
     ///
-    /// In other words, remove all pairs `(k, v)` for which `f(&k, &mut v)` returns `false`.
+    /// In other words, remove all pairs `(k, v)` for which `f(&k, &v)` returns `false`.
     /// The elements are visited in unsorted (and unspecified) order.
-    pub fn retain(&mut self, f: impl FnMut(&Name, &mut Glyph) -> bool) {
-        self.glyphs.retain(f);
+    ///
+    /// The file names of removed glyphs are released, and glyphs shared with a
+    /// clone of this layer are not copied.
+    pub fn retain(&mut self, mut f: impl FnMut(&Name, &Glyph) -> bool) {
+        let (contents, path_set) = (&mut self.contents, &mut self.path_set);
+        self.glyphs.retain(|name, glyph| {
+            let keep = f(name, glyph);
+            if !keep {
+                if let Some(path) = contents.remove(name) {
+                    path_set.remove(&path.to_string_lossy().to_lowercase());
+                }
+            }
+            keep
+        });
     }
 
     /// Returns the path to the .glif file of a given glyph `name`.
@@ -576,9 +764,183 @@ impl Default for Layer {
     }
 }
 
+/// A view into a single glyph of a [`Layer`], which may either be present or
+/// absent.
+///
+/// Created with [`Layer::entry`].
+#[derive(Debug)]
+pub enum GlyphEntry<'a> {
+    /// The layer has a glyph with this name.
+    Occupied(OccupiedGlyphEntry<'a>),
+    /// The layer has no glyph with this name.
+    Vacant(VacantGlyphEntry<'a>),
+}
+
+/// A view into a glyph that is present in a [`Layer`]; part of [`GlyphEntry`].
+#[derive(Debug)]
+pub struct OccupiedGlyphEntry<'a> {
+    layer: &'a mut Layer,
+    name: Name,
+}
+
+/// A view into a glyph that is absent from a [`Layer`]; part of [`GlyphEntry`].
+#[derive(Debug)]
+pub struct VacantGlyphEntry<'a> {
+    layer: &'a mut Layer,
+    name: Name,
+}
+
+impl<'a> GlyphEntry<'a> {
+    /// Returns the name of the glyph.
+    pub fn key(&self) -> &Name {
+        match self {
+            GlyphEntry::Occupied(entry) => entry.key(),
+            GlyphEntry::Vacant(entry) => entry.key(),
+        }
+    }
+
+    /// Inserts `default` if the glyph is absent, and returns a mutable
+    /// reference to the glyph.
+    pub fn or_insert(self, default: Glyph) -> &'a mut Glyph {
+        self.or_insert_with(|| default)
+    }
+
+    /// Inserts the result of `default` if the glyph is absent, and returns a
+    /// mutable reference to the glyph.
+    pub fn or_insert_with(self, default: impl FnOnce() -> Glyph) -> &'a mut Glyph {
+        match self {
+            GlyphEntry::Occupied(entry) => entry.into_mut(),
+            GlyphEntry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    /// Calls `f` with the glyph if it is present.
+    pub fn and_modify(mut self, f: impl FnOnce(&mut Glyph)) -> Self {
+        if let GlyphEntry::Occupied(entry) = &mut self {
+            f(entry.get_mut());
+        }
+        self
+    }
+}
+
+impl<'a> OccupiedGlyphEntry<'a> {
+    /// Returns the name of the glyph.
+    pub fn key(&self) -> &Name {
+        &self.name
+    }
+
+    /// Returns a reference to the glyph.
+    pub fn get(&self) -> &Glyph {
+        &self.layer.glyphs[&self.name]
+    }
+
+    /// Returns a mutable reference to the glyph.
+    ///
+    /// If the glyph is shared with a clone of the layer, it is copied first.
+    pub fn get_mut(&mut self) -> &mut Glyph {
+        self.layer.get_glyph_mut(&self.name).expect("occupied entries have a glyph")
+    }
+
+    /// Converts the entry into a mutable reference to the glyph, with the
+    /// lifetime of the layer.
+    pub fn into_mut(self) -> &'a mut Glyph {
+        self.layer.get_glyph_mut(&self.name).expect("occupied entries have a glyph")
+    }
+
+    /// Replaces the glyph with `glyph`, returning the old glyph.
+    pub fn insert(&mut self, glyph: Glyph) -> Glyph {
+        std::mem::replace(self.get_mut(), glyph)
+    }
+
+    /// Removes the glyph from the layer and returns it.
+    pub fn remove(self) -> Glyph {
+        self.layer.remove_glyph(&self.name).expect("occupied entries have a glyph")
+    }
+}
+
+impl<'a> VacantGlyphEntry<'a> {
+    /// Returns the name of the glyph.
+    pub fn key(&self) -> &Name {
+        &self.name
+    }
+
+    /// Takes ownership of the name.
+    pub fn into_key(self) -> Name {
+        self.name
+    }
+
+    /// Inserts `glyph` under the entry's name, giving it a file name, and
+    /// returns a mutable reference to it.
+    pub fn insert(self, glyph: Glyph) -> &'a mut Glyph {
+        self.layer.add_file_name(&self.name);
+        let glyph = self.layer.glyphs.entry(self.name).or_insert(Arc::new(glyph));
+        Arc::make_mut(glyph)
+    }
+}
+
+/// The last [`SpacingReport`] computed for a layer, together with the glyphs
+/// it was computed from.
+///
+/// The glyphs are held weakly, so that the cache does not force them to be
+/// copied on write. Accessing a glyph mutably moves it to a new allocation
+/// whenever weak references to it exist, so a report is reused only while the
+/// layer holds exactly the same glyph allocations as when it was computed.
+#[derive(Default)]
+pub(crate) struct SpacingCache(Mutex<Option<(Vec<Weak<Glyph>>, SpacingReport)>>);
+
+impl SpacingCache {
+    /// Returns the cached report if `glyphs` are unchanged, or else computes,
+    /// caches and returns a new one with `f`.
+    pub(crate) fn get_or_insert_with(
+        &self,
+        glyphs: &BTreeMap<Name, Arc<Glyph>>,
+        f: impl FnOnce() -> SpacingReport,
+    ) -> SpacingReport {
+        let mut cached = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some((snapshot, report)) = cached.as_ref() {
+            if snapshot.len() == glyphs.len()
+                && snapshot
+                    .iter()
+                    .zip(glyphs.values())
+                    .all(|(old, new)| old.as_ptr() == Arc::as_ptr(new))
+            {
+                return report.clone();
+            }
+        }
+        let report = f();
+        *cached = Some((glyphs.values().map(Arc::downgrade).collect(), report.clone()));
+        report
+    }
+
+    #[cfg(test)]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.lock().unwrap().is_none()
+    }
+}
+
+impl Clone for SpacingCache {
+    fn clone(&self) -> Self {
+        let cached = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        SpacingCache(Mutex::new(cached.clone()))
+    }
+}
+
+// The cache is derived from the glyphs, so it never makes two layers unequal.
+impl PartialEq for SpacingCache {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl std::fmt::Debug for SpacingCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SpacingCache").finish_non_exhaustive()
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{Codepoints, DataRequest};
+    use crate::{Codepoints, Component, DataRequest};
 
     use super::*;
     use std::path::Path;
@@ -860,31 +1222,36 @@ mod tests {
         let names = NameList::default();
 
         let request = DataRequest::all();
-        let layerset = LayerContents::load(ufo_path, &names, &request.layers).unwrap();
+        let layerset =
+            LayerContents::load(ufo_path, &names, &request.layers, false, &mut Vec::new()).unwrap();
         assert_eq!(layerset.len(), 2);
         assert_eq!(layerset.default_layer().len(), 48);
 
         let request = DataRequest::none();
-        let layerset = LayerContents::load(ufo_path, &names, &request.layers).unwrap();
+        let layerset =
+            LayerContents::load(ufo_path, &names, &request.layers, false, &mut Vec::new()).unwrap();
         // default layer is always present
         assert_eq!(layerset.len(), 1);
         assert_eq!(layerset.default_layer().len(), 0);
 
         let request = DataRequest::none().default_layer(true);
-        let layerset = LayerContents::load(ufo_path, &names, &request.layers).unwrap();
+        let layerset =
+            LayerContents::load(ufo_path, &names, &request.layers, false, &mut Vec::new()).unwrap();
         assert_eq!(layerset.len(), 1);
         assert_eq!(layerset.default_layer().len(), 48);
 
         // all is overwridden by default_layer
         let request = DataRequest::all().default_layer(true);
-        let layerset = LayerContents::load(ufo_path, &names, &request.layers).unwrap();
+        let layerset =
+            LayerContents::load(ufo_path, &names, &request.layers, false, &mut Vec::new()).unwrap();
         // default layer is always present
         assert_eq!(layerset.len(), 1);
         assert_eq!(layerset.default_layer().len(), 48);
 
         let layer_name = String::from("background");
         let request = DataRequest::none().filter_layers(|name, _path| name == layer_name);
-        let layerset = LayerContents::load(ufo_path, &names, &request.layers).unwrap();
+        let layerset =
+            LayerContents::load(ufo_path, &names, &request.layers, false, &mut Vec::new()).unwrap();
         // default layer is always present
         assert_eq!(layerset.len(), 2);
         assert_eq!(layerset.default_layer().len(), 0);
@@ -921,14 +1288,14 @@ mod tests {
                 Layer {
                     name: Name::new("fizz").unwrap(),
                     glyphs: maplit::btreemap! {
-                        Name::new("a").unwrap() => Glyph::new("a"),
+                        Name::new("a").unwrap() => Arc::new(Glyph::new("a")),
                     },
                     ..Default::default()
                 },
                 Layer {
                     name: Name::new("buzz").unwrap(),
                     glyphs: maplit::btreemap! {
-                        Name::new("b").unwrap() => Glyph::new("b"),
+                        Name::new("b").unwrap() => Arc::new(Glyph::new("b")),
                     },
                     ..Default::default()
                 },
@@ -937,10 +1304,92 @@ mod tests {
             ..Default::default()
         };
 
-        layers.remove_empty_layers();
+        assert_eq!(layers.remove_empty_layers(), vec![Name::new_raw("fizzbuzz")]);
 
         assert_eq!(layers.len(), 3, "wrong number of layers deleted");
         let names = layers.iter().map(|l| l.name().as_str()).collect::<Vec<_>>();
         assert_eq!(names.as_slice(), &[DEFAULT_LAYER_NAME, "fizz", "buzz"]);
     }
+
+    #[test]
+    fn clone_is_copy_on_write() {
+        let mut layer = Layer::default();
+        layer.insert_glyph(Glyph::new("a"));
+        let original = layer.clone();
+        assert!(Arc::ptr_eq(&layer.glyphs["a"], &original.glyphs["a"]));
+
+        layer.get_glyph_mut("a").unwrap().width = 500.0;
+        assert!(!Arc::ptr_eq(&layer.glyphs["a"], &original.glyphs["a"]));
+        assert_eq!(layer.get_glyph("a").unwrap().width, 500.0);
+        assert_eq!(original.get_glyph("a").unwrap().width, 0.0);
+    }
+
+    #[test]
+    fn retain_does_not_copy_shared_glyphs() {
+        let mut layer = Layer::default();
+        layer.insert_glyph(Glyph::new("a"));
+        layer.insert_glyph(Glyph::new("b"));
+        let original = layer.clone();
+
+        layer.retain(|name, _| name.as_str() != "b");
+        assert!(Arc::ptr_eq(&layer.glyphs["a"], &original.glyphs["a"]));
+        assert!(!layer.contains_glyph("b") && layer.get_path("b").is_none());
+        assert!(original.contains_glyph("b"));
+    }
+
+    #[test]
+    fn glyph_entry() {
+        let mut layer = Layer::default();
+        layer.entry(Name::new_raw("a")).or_insert(Glyph::new("a")).width = 100.0;
+        assert_eq!(layer.get_glyph("a").unwrap().width, 100.0);
+        assert_eq!(layer.get_path("a"), Some(Path::new("a.glif")));
+
+        let original = layer.clone();
+        match layer.entry(Name::new_raw("a")) {
+            GlyphEntry::Occupied(entry) => {
+                assert_eq!(entry.get().width, 100.0);
+                // reading doesn't copy a glyph shared with a clone
+                assert!(Arc::ptr_eq(&entry.layer.glyphs["a"], &original.glyphs["a"]));
+                assert_eq!(entry.remove().width, 100.0);
+            }
+            GlyphEntry::Vacant(_) => panic!("glyph 'a' exists"),
+        }
+        assert!(layer.get_path("a").is_none());
+        assert_eq!(original.get_glyph("a").unwrap().width, 100.0);
+
+        let entry = layer.entry(Name::new_raw("b")).and_modify(|_| panic!("glyph 'b' is absent"));
+        assert!(matches!(entry, GlyphEntry::Vacant(_)));
+        assert_eq!(entry.key().as_str(), "b");
+    }
+
+    #[test]
+    fn rename_glyph_updates_components() {
+        let mut layer = Layer::default();
+        layer.insert_glyph(Glyph::new("a"));
+        layer.insert_glyph(Glyph::new("b"));
+        let mut aacute = Glyph::new("aacute");
+        aacute.components.push(Component::new(Name::new_raw("a"), Default::default(), None, None));
+        aacute.components.push(Component::new(
+            Name::new_raw("acute"),
+            Default::default(),
+            None,
+            None,
+        ));
+        layer.insert_glyph(aacute);
+
+        layer.rename_glyph("a", "a.alt", false).unwrap();
+        assert!(!layer.contains_glyph("a"));
+        assert_eq!(layer.get_path("a"), None);
+        assert_eq!(layer.get_path("a.alt"), Some(Path::new("a.alt.glif")));
+        let bases: Vec<_> =
+            layer.get_glyph("aacute").unwrap().components.iter().map(|c| c.base.as_str()).collect();
+        assert_eq!(bases, ["a.alt", "acute"]);
+
+        assert!(matches!(
+            layer.rename_glyph("a.alt", "b", false),
+            Err(NamingError::Duplicate(name)) if name == "b"
+        ));
+        assert!(layer.contains_glyph("a.alt"));
+        assert!(matches!(layer.rename_glyph("a", "c", false), Err(NamingError::Missing(_))));
+    }
 }
@@ -86,15 +86,18 @@ mod name;
 mod names;
 mod serde_xml_plist;
 mod shared_types;
+mod spacing;
 mod upconversion;
 pub(crate) mod util;
 mod write;
 
 pub use data_request::DataRequest;
-pub use font::{Font, FormatVersion, MetaInfo};
+pub use font::{Font, FormatVersion, MetaInfo, SavePlan};
 pub use fontinfo::FontInfo;
 pub use glyph::{
-    AffineTransform, Anchor, Codepoints, Component, Contour, ContourPoint, Glyph, Image, PointType,
+    AffineTransform, Anchor, Bounds, Codepoints, Component, Contour, ContourPoint,
+    CurveTypeSummary, DecomposeOptions, Glyph, GlyphPointPen, Image, Pen, PointPen, PointType,
+    TransformComponents,
 };
 
 pub use name::Name;
@@ -103,7 +106,8 @@ pub use groups::Groups;
 pub use guideline::{Guideline, Line};
 pub use identifier::Identifier;
 pub use kerning::Kerning;
-pub use layer::{Layer, LayerContents};
+pub use layer::{GlyphEntry, Layer, LayerContents, OccupiedGlyphEntry, VacantGlyphEntry};
 pub use shared_types::{Color, Plist};
+pub use spacing::{NegativeSidebearing, SpacingReport, WidthCount};
 pub use util::user_name_to_file_name;
 pub use write::{QuoteChar, WriteOptions};
@@ -30,8 +30,6 @@ mod de {
 
     struct DictWrapper(Dictionary);
 
-    struct ValueWrapper(Value);
-
     struct ArrayWrapper(Vec<Value>);
 
     struct IntWrapper(plist::Integer);
@@ -56,9 +54,8 @@ mod de {
     }
 
     // the logic for deserializing a dict is a subset of the general deser logic,
-    // so we reuse this type for both cases.
+    // which is shared with arrays and nested values via `read_xml_value`.
     pub(super) enum ValueVisitor {
-        AnyValue,
         DictOnly,
     }
 
@@ -67,7 +64,6 @@ mod de {
 
         fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
             match self {
-                ValueVisitor::AnyValue => formatter.write_str("plist value"),
                 ValueVisitor::DictOnly => formatter.write_str("plist dictionary"),
             }
         }
@@ -131,15 +127,6 @@ mod de {
         value.map(Some)
     }
 
-    impl<'de> Deserialize<'de> for ValueWrapper {
-        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-        where
-            D: Deserializer<'de>,
-        {
-            deserializer.deserialize_any(ValueVisitor::AnyValue).map(ValueWrapper)
-        }
-    }
-
     impl<'de> Deserialize<'de> for DictWrapper {
         fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
         where
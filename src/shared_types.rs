@@ -5,10 +5,37 @@ use serde::ser::Serializer;
 use serde::{Deserialize, Serialize};
 
 pub static PUBLIC_OBJECT_LIBS_KEY: &str = "public.objectLibs";
+pub static PUBLIC_GLYPH_ORDER_KEY: &str = "public.glyphOrder";
+pub static PUBLIC_SKIP_EXPORT_GLYPHS_KEY: &str = "public.skipExportGlyphs";
 
 /// A Plist dictionary.
 pub type Plist = plist::Dictionary;
 
+/// Returns the strings in the array stored in `lib` under `key`.
+///
+/// Items that aren't strings are skipped; a missing key or a value that isn't
+/// an array gives an empty list.
+pub(crate) fn lib_string_list(lib: &Plist, key: &str) -> Vec<String> {
+    lib.get(key)
+        .and_then(plist::Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(plist::Value::as_string)
+        .map(String::from)
+        .collect()
+}
+
+/// Stores `items` in `lib` as an array of strings under `key`, or removes the
+/// key if `items` is empty.
+pub(crate) fn set_lib_string_list(lib: &mut Plist, key: &str, items: Vec<String>) {
+    if items.is_empty() {
+        lib.remove(key);
+    } else {
+        let items: Vec<plist::Value> = items.into_iter().map(plist::Value::String).collect();
+        lib.insert(key.into(), items.into());
+    }
+}
+
 /// A color in RGBA (Red-Green-Blue-Alpha) format.
 ///
 /// See <https://unifiedfontobject.org/versions/ufo3/conventions/#colors>.
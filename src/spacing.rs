@@ -0,0 +1,50 @@
+//! Aggregate spacing statistics for a layer.
+
+use serde::{Deserialize, Serialize};
+
+use crate::Name;
+
+/// A summary of the spacing of the glyphs in a layer, for quality assurance.
+///
+/// Created with [`Font::spacing_report`]. The report can be serialized, for
+/// instance to compare it between builds in continuous integration.
+///
+/// [`Font::spacing_report`]: crate::Font::spacing_report
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct SpacingReport {
+    /// The number of glyphs in the layer.
+    pub glyph_count: usize,
+    /// How many glyphs have each distinct advance width, ordered by width.
+    pub advance_widths: Vec<WidthCount>,
+    /// The glyphs with an advance width of zero.
+    pub zero_width_glyphs: Vec<Name>,
+    /// The glyphs with at least one negative sidebearing.
+    pub negative_sidebearings: Vec<NegativeSidebearing>,
+    /// The glyphs whose outline could not be measured, because a contour has an
+    /// invalid point sequence, or because a component's base glyph is missing
+    /// or components reference each other in a cycle.
+    pub unmeasured_glyphs: Vec<Name>,
+}
+
+/// The number of glyphs sharing an advance width, in a [`SpacingReport`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct WidthCount {
+    /// The advance width.
+    pub width: f64,
+    /// The number of glyphs with this advance width.
+    pub count: usize,
+}
+
+/// A glyph with a negative sidebearing, in a [`SpacingReport`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct NegativeSidebearing {
+    /// The name of the glyph.
+    pub glyph: Name,
+    /// The left sidebearing.
+    pub left: f64,
+    /// The right sidebearing.
+    pub right: f64,
+}
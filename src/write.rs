@@ -35,6 +35,8 @@ pub struct WriteOptions {
     pub(crate) indent_char: u8,
     pub(crate) indent_count: usize,
     pub(crate) quote_style: QuoteChar,
+    pub(crate) float_precision: Option<u8>,
+    pub(crate) reuse_file_names: bool,
 }
 
 impl Default for WriteOptions {
@@ -44,6 +46,8 @@ impl Default for WriteOptions {
             indent_char: WriteOptions::TAB,
             indent_count: 1,
             quote_style: QuoteChar::Double,
+            float_precision: None,
+            reuse_file_names: false,
         }
     }
 }
@@ -123,6 +127,73 @@ impl WriteOptions {
         self
     }
 
+    /// Builder-style method to round real numbers written to property lists.
+    ///
+    /// By default, reals are written with as many digits as are needed to read
+    /// them back exactly, so a value like `0.1 + 0.2` is written as
+    /// `0.30000000000000004`. With this option set, reals in `.plist` files (and
+    /// in glyph libs) are first rounded to at most `digits` decimal places, and
+    /// written without trailing zeros.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use norad::WriteOptions;
+    /// let options = WriteOptions::new().float_precision(6);
+    /// ```
+    pub fn float_precision(mut self, digits: u8) -> Self {
+        self.float_precision = Some(digits);
+        self
+    }
+
+    /// Builder-style method to keep the `.glif` file names already used by the
+    /// UFO being overwritten.
+    ///
+    /// By default, each glyph is written to the file it was loaded from, and new,
+    /// renamed or re-inserted glyphs get a file name generated from their glyph
+    /// name. When a UFO is regenerated from another source, or was written by a
+    /// tool that names files differently, this can rename many files on disk
+    /// even though their contents are unchanged.
+    ///
+    /// With this option set, [`Font::save_with_options`] reads the
+    /// `contents.plist` of every layer directory it is about to replace, and
+    /// writes each glyph listed there to the file name it had before. Other
+    /// glyphs keep their current file name, unless it clashes with one that was
+    /// reused, in which case a new one is generated.
+    ///
+    /// [`Font::save_with_options`]: crate::Font::save_with_options
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use norad::WriteOptions;
+    /// let options = WriteOptions::new().reuse_file_names(true);
+    /// ```
+    pub fn reuse_file_names(mut self, reuse: bool) -> Self {
+        self.reuse_file_names = reuse;
+        self
+    }
+
+    /// Rounds every real in `value` according to [`WriteOptions::float_precision`].
+    pub(crate) fn round_reals(&self, value: &mut plist::Value) {
+        let Some(digits) = self.float_precision else {
+            return;
+        };
+        let scale = 10f64.powi(digits.into());
+        match value {
+            plist::Value::Real(real) => {
+                let scaled = *real * scale;
+                // values too large to carry that many decimals are already exact
+                if scaled.abs() < 2f64.powi(53) {
+                    *real = scaled.round() / scale;
+                }
+            }
+            plist::Value::Array(array) => array.iter_mut().for_each(|v| self.round_reals(v)),
+            plist::Value::Dictionary(dict) => dict.values_mut().for_each(|v| self.round_reals(v)),
+            _ => (),
+        }
+    }
+
     /// Return a reference to [`XmlWriteOptions`] for use with the `plist` crate.
     pub fn xml_options(&self) -> &XmlWriteOptions {
         &self.xml_opts
@@ -155,8 +226,14 @@ pub(crate) fn write_xml_to_file(
 ) -> Result<(), CustomSerializationError> {
     let mut file = File::create(path).map_err(CustomSerializationError::CreateFile)?.fast_close();
     let buf_writer = BufWriter::new(&mut file);
-    plist::to_writer_xml_with_options(buf_writer, value, options.xml_options())
-        .map_err(CustomSerializationError::SerializePlist)?;
+    if options.float_precision.is_some() {
+        let mut value = plist::to_value(value).map_err(CustomSerializationError::SerializePlist)?;
+        options.round_reals(&mut value);
+        value.to_writer_xml_with_options(buf_writer, options.xml_options())
+    } else {
+        plist::to_writer_xml_with_options(buf_writer, value, options.xml_options())
+    }
+    .map_err(CustomSerializationError::SerializePlist)?;
     write_quote_style(&file, options).map_err(CustomSerializationError::WriteQuotes)?;
     file.sync_all().map_err(CustomSerializationError::Sync)?;
     Ok(())
@@ -326,4 +403,39 @@ mod tests {
         assert!(plist_write.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n"));
         tmp.close().unwrap();
     }
+
+    #[test]
+    fn write_fontinfo_plist_reals() {
+        let tmp = TempDir::new().unwrap();
+        let filepath = tmp.path().join("fontinfo.plist");
+        let mut font_info = crate::FontInfo::default();
+        let read_italic_angle = || {
+            let plist = Value::from_file(&filepath).unwrap();
+            let dict = plist.into_dictionary().unwrap();
+            let angle = dict.get("italicAngle").unwrap().as_real().unwrap();
+            let xml = fs::read_to_string(&filepath).unwrap();
+            (angle, xml)
+        };
+
+        font_info.italic_angle = Some(-12.5);
+        write_xml_to_file(&filepath, &font_info, &WriteOptions::default()).unwrap();
+        let (angle, xml) = read_italic_angle();
+        assert_eq!(angle, -12.5);
+        assert!(xml.contains("<real>-12.5</real>"), "{xml}");
+
+        font_info.italic_angle = Some(-12.500000001);
+        write_xml_to_file(&filepath, &font_info, &WriteOptions::default()).unwrap();
+        assert!(read_italic_angle().1.contains("<real>-12.500000001</real>"));
+
+        let opt = WriteOptions::default().float_precision(6);
+        write_xml_to_file(&filepath, &font_info, &opt).unwrap();
+        let (angle, xml) = read_italic_angle();
+        assert_eq!(angle, -12.5);
+        assert!(xml.contains("<real>-12.5</real>"), "{xml}");
+
+        font_info.italic_angle = Some(0.1 + 0.2);
+        write_xml_to_file(&filepath, &font_info, &opt).unwrap();
+        assert!(read_italic_angle().1.contains("<real>0.3</real>"));
+        tmp.close().unwrap();
+    }
 }
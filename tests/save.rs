@@ -1,6 +1,6 @@
 //! Testing saving files.
 
-use norad::{Codepoints, Font, FormatVersion, Glyph, Identifier, Plist};
+use norad::{Codepoints, Font, FormatVersion, Glyph, Identifier, Plist, WriteOptions};
 use plist::Value;
 use tempfile::TempDir;
 
@@ -198,3 +198,73 @@ fn object_libs_reject_existing_key() {
     ufo.default_layer_mut().insert_glyph(glyph);
     assert!(ufo.save(&dir).is_err());
 }
+
+#[test]
+fn save_reusing_file_names() {
+    use std::collections::{BTreeMap, BTreeSet};
+    use std::path::{Path, PathBuf};
+
+    fn files_in(dir: &Path) -> BTreeSet<PathBuf> {
+        let mut files = BTreeSet::new();
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                files.extend(files_in(&path));
+            } else {
+                files.insert(path);
+            }
+        }
+        files
+    }
+
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("Test.ufo");
+    let contents_path = path.join("glyphs/contents.plist");
+    let read_contents =
+        || -> BTreeMap<String, PathBuf> { plist::from_file(&contents_path).unwrap() };
+
+    // another tool wrote the UFO with its own file names
+    let font = Font::load("testdata/MutatorSansLightWide.ufo").unwrap();
+    font.save(&path).unwrap();
+    let mut contents = read_contents();
+    for (name, file) in [("A", "glyph_A.glif"), ("B", "glyph_B.glif")] {
+        std::fs::rename(path.join("glyphs").join(&contents[name]), path.join("glyphs").join(file))
+            .unwrap();
+        contents.insert(name.into(), file.into());
+    }
+    // a file name that a new glyph would clash with
+    std::fs::rename(path.join("glyphs").join(&contents["C"]), path.join("glyphs/new.glif"))
+        .unwrap();
+    contents.insert("C".into(), "new.glif".into());
+    plist::to_file_xml(&contents_path, &contents).unwrap();
+    // paths that aren't plain file names are never reused
+    let mut unsafe_contents = contents.clone();
+    unsafe_contents.insert("D".into(), "../D_.glif".into());
+    plist::to_file_xml(&contents_path, &unsafe_contents).unwrap();
+
+    // the UFO is regenerated from a font loaded elsewhere, with a new glyph
+    let mut font = Font::load("testdata/MutatorSansLightWide.ufo").unwrap();
+    font.default_layer_mut().insert_glyph(Glyph::new("new"));
+    let options = WriteOptions::default().reuse_file_names(true);
+    let plan = font.plan_save_with_options(&path, &options).unwrap();
+    font.save_with_options(&path, &options).unwrap();
+    // the dry run lists exactly the files that were written
+    let planned: BTreeSet<_> = plan.writes().cloned().collect();
+    assert_eq!(planned, files_in(&path));
+    assert!(plan.deletions.iter().all(|file| !file.exists()));
+
+    let saved = read_contents();
+    assert_eq!(saved.len(), contents.len() + 1);
+    for (name, file) in &contents {
+        assert_eq!(&saved[name], file, "{name}");
+    }
+    assert_eq!(saved["new"], PathBuf::from("new01.glif"));
+    assert!(!dir.path().join("D_.glif").exists());
+    let loaded = Font::load(&path).unwrap();
+    assert_eq!(loaded.default_layer().get_glyph("A"), font.get_glyph("A"));
+    assert_eq!(loaded.default_layer().get_path("A"), Some(PathBuf::from("glyph_A.glif").as_path()));
+
+    // without the option, file names are the ones the font was loaded with
+    font.save(&path).unwrap();
+    assert_eq!(read_contents()["A"], PathBuf::from("A_.glif"));
+}